@@ -0,0 +1,604 @@
+use crate::resource::{
+    self, group, resourcelink::Link, resourcelink::LinkKind, rule, scene, schedule, sensor, Action,
+    Modifier,
+};
+use crate::{Bridge, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, collections::HashMap};
+use thiserror::Error as ThisError;
+
+/// Version of the archive format that is written by this library.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A portable backup of the configuration of a bridge.
+///
+/// Lights and sensors are not created by an import, they are only used to map the identifiers of
+/// the archive to the identifiers of the bridge by their unique identifier, or by the name and
+/// type of sensors without one.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Archive {
+    /// Version of the archive format.
+    pub version: u32,
+    /// Identifier of the bridge that the archive was exported from.
+    pub bridge_id: String,
+    /// Time when the archive was exported in UTC.
+    pub created: chrono::NaiveDateTime,
+    /// Lights of the bridge.
+    pub lights: Vec<LightEntry>,
+    /// Sensors of the bridge.
+    pub sensors: Vec<SensorEntry>,
+    /// Groups of the bridge.
+    pub groups: Vec<GroupEntry>,
+    /// Scenes of the bridge.
+    pub scenes: Vec<SceneEntry>,
+    /// Schedules of the bridge.
+    pub schedules: Vec<ScheduleEntry>,
+    /// Rules of the bridge.
+    pub rules: Vec<RuleEntry>,
+    /// Resourcelinks of the bridge.
+    pub resourcelinks: Vec<ResourcelinkEntry>,
+}
+
+/// A light in an archive.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct LightEntry {
+    /// Identifier of the light on the exported bridge.
+    pub id: resource::LightId,
+    /// Unique identifier of the light.
+    pub unique_id: String,
+    /// Name of the light.
+    pub name: String,
+}
+
+/// A sensor in an archive.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SensorEntry {
+    /// Identifier of the sensor on the exported bridge.
+    pub id: resource::SensorId,
+    /// Unique identifier of the sensor.
+    pub unique_id: Option<String>,
+    /// Name of the sensor.
+    pub name: String,
+    /// Type name of the sensor.
+    #[serde(default)]
+    pub type_name: String,
+    /// Whether the sensor is on.
+    ///
+    /// This is the only attribute of the sensor configuration that is exported and restored,
+    /// other attributes like the sensitivity of motion sensors are lost.
+    pub on: bool,
+}
+
+/// A group in an archive.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct GroupEntry {
+    /// Identifier of the group on the exported bridge.
+    pub id: resource::GroupId,
+    /// Name of the group.
+    pub name: String,
+    /// Kind of the group.
+    pub kind: group::Kind,
+    /// Class of the group.
+    pub class: Option<group::Class>,
+    /// Identifiers of the lights in the group.
+    pub lights: Vec<resource::LightId>,
+    /// Identifiers of the sensors in the group.
+    pub sensors: Vec<resource::SensorId>,
+    /// Whether the group is automatically deleted when not referenced anymore.
+    pub recycle: Option<bool>,
+}
+
+/// A scene in an archive.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SceneEntry {
+    /// Identifier of the scene on the exported bridge.
+    pub id: resource::SceneId,
+    /// Name of the scene.
+    pub name: String,
+    /// Kind of the scene.
+    pub kind: scene::Kind,
+    /// Identifier of the group that the scene is linked to.
+    pub group: Option<resource::GroupId>,
+    /// Identifiers of the lights in the scene.
+    pub lights: Vec<resource::LightId>,
+    /// States of the lights in the scene.
+    pub light_states: HashMap<resource::LightId, scene::LightStateModifier>,
+    /// App specific data linked to the scene.
+    pub app_data: scene::AppData,
+    /// Whether the scene is automatically deleted when not referenced anymore.
+    pub recycle: bool,
+}
+
+/// A schedule in an archive.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ScheduleEntry {
+    /// Identifier of the schedule on the exported bridge.
+    pub id: resource::ScheduleId,
+    /// Name of the schedule.
+    pub name: String,
+    /// Description of the schedule.
+    pub description: String,
+    /// Action to execute when the scheduled event occurs.
+    pub action: Action,
+    /// Time when the scheduled event will occur.
+    pub local_time: String,
+    /// Status of the schedule.
+    pub status: schedule::Status,
+    /// Whether the schedule will be removed after it expires.
+    pub auto_delete: Option<bool>,
+}
+
+/// A rule in an archive.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct RuleEntry {
+    /// Identifier of the rule on the exported bridge.
+    pub id: resource::RuleId,
+    /// Name of the rule.
+    pub name: String,
+    /// Status of the rule.
+    pub status: rule::Status,
+    /// Conditions of the rule.
+    pub conditions: Vec<rule::Condition>,
+    /// Actions of the rule.
+    pub actions: Vec<Action>,
+}
+
+/// A resourcelink in an archive.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourcelinkEntry {
+    /// Identifier of the resourcelink on the exported bridge.
+    pub id: resource::ResourcelinkId,
+    /// Name of the resourcelink.
+    pub name: String,
+    /// Description of the resourcelink.
+    pub description: String,
+    /// Class identifier of the resourcelink.
+    pub class_id: u16,
+    /// Whether the resourcelink is automatically deleted when not referenced anymore.
+    pub recycle: bool,
+    /// References to resources which are used by the resourcelink.
+    pub links: Vec<Link>,
+}
+
+impl Archive {
+    /// Reads the configuration of a bridge into an archive.
+    ///
+    /// Luminaire and lightsource groups are skipped, since they are created by the bridge. Groups
+    /// of a kind that is unknown to this library are exported, but they are skipped by an import.
+    pub fn export(bridge: &Bridge) -> Result<Self> {
+        let datastore = bridge.get_datastore()?;
+        let mut archive = Self {
+            version: ARCHIVE_VERSION,
+            bridge_id: datastore.config.bridge_id,
+            created: datastore.config.current_time,
+            lights: datastore
+                .lights
+                .into_iter()
+                .map(|v| LightEntry {
+                    id: v.id,
+                    unique_id: v.unique_id,
+                    name: v.name,
+                })
+                .collect(),
+            sensors: datastore
+                .sensors
+                .into_iter()
+                .map(|v| SensorEntry {
+                    id: v.id,
+                    unique_id: v.unique_id,
+                    name: v.name,
+                    type_name: v.type_name,
+                    on: v.config.on,
+                })
+                .collect(),
+            groups: Vec::new(),
+            scenes: Vec::new(),
+            schedules: datastore
+                .schedules
+                .into_iter()
+                .map(|v| ScheduleEntry {
+                    id: v.id,
+                    name: v.name,
+                    description: v.description,
+                    action: v.action,
+                    local_time: v.local_time,
+                    status: v.status,
+                    auto_delete: v.auto_delete,
+                })
+                .collect(),
+            rules: datastore
+                .rules
+                .into_iter()
+                .map(|v| RuleEntry {
+                    id: v.id,
+                    name: v.name,
+                    status: v.status,
+                    conditions: v.conditions,
+                    actions: v.actions,
+                })
+                .collect(),
+            resourcelinks: datastore
+                .resourcelinks
+                .into_iter()
+                .map(|v| ResourcelinkEntry {
+                    id: v.id,
+                    name: v.name,
+                    description: v.description,
+                    class_id: v.class_id,
+                    recycle: v.recycle,
+                    links: v.links,
+                })
+                .collect(),
+        };
+        for group in datastore.groups {
+            if !matches!(group.kind, group::Kind::Immutable(_)) {
+                archive.groups.push(GroupEntry {
+                    id: group.id,
+                    name: group.name,
+                    kind: group.kind,
+                    class: group.class,
+                    lights: group.lights,
+                    sensors: group.sensors,
+                    recycle: group.recycle,
+                });
+            }
+        }
+        for scene in datastore.scenes {
+            let light_states = bridge.get_scene(&scene.id)?.light_states;
+            archive.scenes.push(SceneEntry {
+                id: scene.id,
+                name: scene.name,
+                kind: scene.kind,
+                group: scene.group,
+                lights: scene.lights.unwrap_or_default(),
+                light_states: light_states.unwrap_or_default(),
+                app_data: scene.app_data,
+                recycle: scene.recycle,
+            });
+        }
+        archive.lights.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        archive.sensors.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        archive.groups.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        archive.scenes.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        archive.schedules.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        archive.rules.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        archive.resourcelinks.sort_by(|a, b| cmp_ids(&a.id, &b.id));
+        Ok(archive)
+    }
+
+    /// Recreates the resources of the archive on a bridge.
+    ///
+    /// Lights and sensors are mapped to the lights and sensors of the bridge with the same unique
+    /// identifier. Sensors without a unique identifier are mapped by their name and type. The
+    /// identifiers in groups, scenes and resourcelinks and the addresses in the actions and
+    /// conditions of schedules and rules are rewritten to the identifiers on the bridge.
+    /// References that cannot be mapped are removed from groups, scenes and resourcelinks.
+    /// Schedules and rules with such references are skipped, as well as groups of a kind that
+    /// cannot be created.
+    ///
+    /// Resources that cannot be created are listed in the report with the error of the bridge,
+    /// so that the import continues and reports what was already created.
+    pub fn import(&self, bridge: &Bridge) -> Result<ImportReport> {
+        if self.version > ARCHIVE_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(self.version).into());
+        }
+        let mut map = IdMap {
+            ids: HashMap::new(),
+            username: bridge.username.clone(),
+        };
+        let mut report = ImportReport::default();
+        let lights = bridge.get_all_lights()?;
+        for entry in &self.lights {
+            if let Some(light) = lights.iter().find(|v| v.unique_id == entry.unique_id) {
+                map.insert(LinkKind::Light, &entry.id, &light.id);
+            }
+        }
+        let sensors = bridge.get_all_sensors()?;
+        for entry in &self.sensors {
+            let sensor = sensors.iter().find(|v| match &entry.unique_id {
+                Some(_) => v.unique_id == entry.unique_id,
+                None => v.name == entry.name && v.type_name == entry.type_name,
+            });
+            if let Some(sensor) = sensor {
+                map.insert(LinkKind::Sensor, &entry.id, &sensor.id);
+                let modifier = sensor::ConfigModifier::new().on(entry.on);
+                if let Err(e) = bridge.set_sensor_config(&sensor.id, &modifier) {
+                    report.fail(LinkKind::Sensor, &entry.id, e);
+                }
+            }
+        }
+        for entry in &self.groups {
+            let kind = match entry.kind {
+                group::Kind::Creatable(v) => v,
+                _ => {
+                    report.skip(LinkKind::Group, &entry.id);
+                    continue;
+                }
+            };
+            let lights = map.ids(LinkKind::Light, &entry.lights, &mut report);
+            let sensors = map.ids(LinkKind::Sensor, &entry.sensors, &mut report);
+            let mut creator = group::Creator::new(&entry.name, lights)
+                .kind(kind)
+                .sensors(sensors);
+            if let Some(v) = &entry.class {
+                creator = creator.class(v.clone());
+            }
+            if let Some(v) = entry.recycle {
+                creator = creator.recycle(v);
+            }
+            let id = match bridge.create_group(&creator) {
+                Ok(v) => v,
+                Err(e) => {
+                    report.fail(LinkKind::Group, &entry.id, e);
+                    continue;
+                }
+            };
+            report.created(LinkKind::Group, &entry.id, &id);
+            map.insert(LinkKind::Group, &entry.id, id);
+        }
+        for entry in &self.scenes {
+            let lights = map.ids(LinkKind::Light, &entry.lights, &mut report);
+            let mut creator = scene::Creator::new(&entry.name, lights)
+                .kind(entry.kind.clone())
+                .recycle(entry.recycle);
+            if let Some(group) = &entry.group {
+                match map.id(LinkKind::Group, group) {
+                    Some(v) => creator = creator.group(v),
+                    None => {
+                        report.skip(LinkKind::Scene, &entry.id);
+                        continue;
+                    }
+                }
+            }
+            if let Some(v) = &entry.app_data.data {
+                creator = creator.app_data(v);
+            }
+            if let Some(v) = entry.app_data.version {
+                creator = creator.app_version(v);
+            }
+            for (light, state) in &entry.light_states {
+                if let Some(v) = map.id(LinkKind::Light, light) {
+                    creator = creator.light_state(v, state.clone());
+                }
+            }
+            let id = match bridge.create_scene(&creator) {
+                Ok(v) => v,
+                Err(e) => {
+                    report.fail(LinkKind::Scene, &entry.id, e);
+                    continue;
+                }
+            };
+            report.created(LinkKind::Scene, &entry.id, &id);
+            map.insert(LinkKind::Scene, &entry.id, id);
+        }
+        for entry in &self.schedules {
+            let action = match map.action(&entry.action) {
+                Some(v) => v,
+                None => {
+                    report.skip(LinkKind::Schedule, &entry.id);
+                    continue;
+                }
+            };
+            let mut creator = schedule::Creator::new(action, entry.local_time.clone())
+                .name(&entry.name)
+                .description(&entry.description)
+                .status(entry.status.clone());
+            if let Some(v) = entry.auto_delete {
+                creator = creator.auto_delete(v);
+            }
+            let id = match bridge.create_schedule(&creator) {
+                Ok(v) => v,
+                Err(e) => {
+                    report.fail(LinkKind::Schedule, &entry.id, e);
+                    continue;
+                }
+            };
+            report.created(LinkKind::Schedule, &entry.id, &id);
+            map.insert(LinkKind::Schedule, &entry.id, id);
+        }
+        for entry in &self.rules {
+            if entry.status == rule::Status::ResourceDeleted {
+                report.skip(LinkKind::Rule, &entry.id);
+                continue;
+            }
+            let conditions: Option<Vec<rule::Condition>> = entry
+                .conditions
+                .iter()
+                .map(|v| {
+                    Some(rule::Condition {
+                        address: map.address(&v.address)?,
+                        ..v.clone()
+                    })
+                })
+                .collect();
+            let actions: Option<Vec<Action>> =
+                entry.actions.iter().map(|v| map.action(v)).collect();
+            let (conditions, actions) = match (conditions, actions) {
+                (Some(c), Some(a)) => (c, a),
+                _ => {
+                    report.skip(LinkKind::Rule, &entry.id);
+                    continue;
+                }
+            };
+            let creator = rule::Creator::new(conditions, actions)
+                .name(&entry.name)
+                .status(entry.status.clone());
+            let id = match bridge.create_rule(&creator) {
+                Ok(v) => v,
+                Err(e) => {
+                    report.fail(LinkKind::Rule, &entry.id, e);
+                    continue;
+                }
+            };
+            report.created(LinkKind::Rule, &entry.id, &id);
+            map.insert(LinkKind::Rule, &entry.id, id);
+        }
+        // Resourcelinks are created in the order of their dependencies, since they can also
+        // reference other resourcelinks.
+        let mut pending: Vec<&ResourcelinkEntry> = self.resourcelinks.iter().collect();
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|entry| {
+                entry.links.iter().all(|link| {
+                    link.kind != LinkKind::Resourcelink || !pending.iter().any(|v| v.id == link.id)
+                })
+            });
+            // Cyclic references can not be resolved and are removed from the remaining links.
+            let entry = pending.remove(ready.unwrap_or(0));
+            let mut creator = resource::resourcelink::Creator::new(&entry.name, entry.class_id)
+                .description(&entry.description)
+                .recycle(entry.recycle);
+            for link in &entry.links {
+                match map.id(link.kind.clone(), &link.id) {
+                    Some(v) => creator = creator.link(link.kind.clone(), v),
+                    None => report.unresolved.push(link.clone()),
+                }
+            }
+            let id = match bridge.create_resourcelink(&creator) {
+                Ok(v) => v,
+                Err(e) => {
+                    report.fail(LinkKind::Resourcelink, &entry.id, e);
+                    continue;
+                }
+            };
+            report.created(LinkKind::Resourcelink, &entry.id, &id);
+            map.insert(LinkKind::Resourcelink, &entry.id, id);
+        }
+        Ok(report)
+    }
+}
+
+/// Errors that can occur while importing an archive.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ArchiveError {
+    /// The archive was created by a newer version of this library.
+    #[error("Unsupported archive version: {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// Result of importing an archive.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Created resources as pairs of the reference in the archive and on the bridge.
+    pub created: Vec<(Link, Link)>,
+    /// Resources of the archive that were not created because they reference a resource that
+    /// does not exist on the bridge, because they were already disabled by the bridge, or
+    /// because they are groups of a kind that cannot be created.
+    pub skipped: Vec<Link>,
+    /// References that were removed because the resource does not exist on the bridge.
+    pub unresolved: Vec<Link>,
+    /// Resources of the archive that could not be created or modified, with the error that
+    /// occurred.
+    pub failed: Vec<(Link, Error)>,
+}
+
+impl ImportReport {
+    fn created(&mut self, kind: LinkKind, old_id: impl AsRef<str>, new_id: impl AsRef<str>) {
+        self.created.push((
+            Link {
+                kind: kind.clone(),
+                id: old_id.as_ref().to_owned(),
+            },
+            Link {
+                kind,
+                id: new_id.as_ref().to_owned(),
+            },
+        ));
+    }
+
+    fn skip(&mut self, kind: LinkKind, id: impl AsRef<str>) {
+        self.skipped.push(Link {
+            kind,
+            id: id.as_ref().to_owned(),
+        });
+    }
+
+    fn fail(&mut self, kind: LinkKind, id: impl AsRef<str>, error: Error) {
+        let link = Link {
+            kind,
+            id: id.as_ref().to_owned(),
+        };
+        self.failed.push((link, error));
+    }
+}
+
+/// Compares identifiers so that numeric identifiers are ordered by their value.
+fn cmp_ids(a: impl AsRef<str>, b: impl AsRef<str>) -> Ordering {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Mapping of identifiers in an archive to identifiers on a bridge.
+struct IdMap {
+    ids: HashMap<(LinkKind, String), String>,
+    username: String,
+}
+
+impl IdMap {
+    fn insert(&mut self, kind: LinkKind, old_id: impl AsRef<str>, new_id: impl AsRef<str>) {
+        self.ids.insert(
+            (kind, old_id.as_ref().to_owned()),
+            new_id.as_ref().to_owned(),
+        );
+    }
+
+    fn id(&self, kind: LinkKind, id: impl AsRef<str>) -> Option<String> {
+        let id = id.as_ref();
+        if kind == LinkKind::Group && id == "0" {
+            return Some(id.to_owned());
+        }
+        self.ids.get(&(kind, id.to_owned())).cloned()
+    }
+
+    /// Maps a list of identifiers and reports those that cannot be mapped.
+    fn ids(
+        &self,
+        kind: LinkKind,
+        ids: &[impl AsRef<str>],
+        report: &mut ImportReport,
+    ) -> Vec<String> {
+        let mut mapped = Vec::new();
+        for id in ids {
+            match self.id(kind.clone(), id) {
+                Some(v) => mapped.push(v),
+                None => report.unresolved.push(Link {
+                    kind: kind.clone(),
+                    id: id.as_ref().to_owned(),
+                }),
+            }
+        }
+        mapped
+    }
+
+    /// Rewrites the identifier and username in an address.
+    fn address(&self, address: &str) -> Option<String> {
+        let mut parts: Vec<String> = address
+            .trim_start_matches('/')
+            .split('/')
+            .map(ToOwned::to_owned)
+            .collect();
+        let mut offset = 0;
+        if parts.len() > 1 && parts[0] == "api" {
+            parts[1] = self.username.clone();
+            offset = 2;
+        }
+        if parts.len() > offset + 1 {
+            if let Some(kind) = LinkKind::from_str(&parts[offset]) {
+                parts[offset + 1] = self.id(kind, &parts[offset + 1])?;
+            }
+        }
+        Some(format!("/{}", parts.join("/")))
+    }
+
+    /// Rewrites the address and the recalled scene of an action.
+    fn action(&self, action: &Action) -> Option<Action> {
+        let mut action = Action {
+            address: self.address(&action.address)?,
+            ..action.clone()
+        };
+        if let Some(v) = action.body.get("scene").and_then(|v| v.as_str()) {
+            let scene = self.id(LinkKind::Scene, v)?;
+            action.body.insert("scene".to_owned(), scene.into());
+        }
+        Some(action)
+    }
+}
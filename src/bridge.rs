@@ -1,10 +1,22 @@
-use crate::{resource, response, Error, Response, Result};
+use crate::scheduler::{CommandKind, Scheduler};
+use crate::tls::{self, CertificateVerifier};
+use crate::{backup, resource, response, Error, Response, Result};
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use std::{fmt, io, net::IpAddr, thread};
 
-type ResponseModified = Response<response::Modified>;
+type Modification = response::Modification;
+
+/// Url of the discovery service of Philips Hue.
+const DISCOVERY_URL: &str = "https://discovery.meethue.com";
+
+/// Connect and read timeout of requests that are not sent by a [`Bridge`], like the discovery of
+/// bridges and the registration of users.
+///
+/// [`Bridge`]: struct.Bridge.html
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Discovers bridges in the local netowork.
 ///
@@ -33,17 +45,20 @@ type ResponseModified = Response<response::Modified>;
 ///     Err(Error::ParseHttpResponse(_)) => eprintln!("Failed to parse http response"),
 ///     Err(Error::ParseJson(_)) => eprintln!("Failed to parse json content"),
 ///     Err(Error::ParseIpAddr(_)) => eprintln!("Failed to parse ip address"),
-///     Err(_) => unreachable!()
+///     Err(Error::Timeout) => eprintln!("The discovery server did not respond in time"),
+///     Err(Error::Transport(e)) => eprintln!("Failed to connect to the discovery server: {}", e),
+///     Err(e) => eprintln!("Failed to discover bridges: {}", e),
 /// };
 /// ```
 pub fn discover() -> Result<Vec<IpAddr>> {
-    let http_response = ureq::get("https://discovery.meethue.com").call();
+    let timeout = Some(DEFAULT_TIMEOUT);
+    let http_response = send(ureq::get(DISCOVERY_URL), None, timeout, timeout)?;
     #[derive(Deserialize)]
     struct BridgeJson {
         #[serde(rename = "internalipaddress")]
         ip_address: String,
     }
-    let bridges: Vec<BridgeJson> = serde_json::from_value(http_response.into_json()?)?;
+    let bridges: Vec<BridgeJson> = serde_json::from_value(http_response)?;
     let mut ip_addresses = Vec::<IpAddr>::new();
     for b in bridges {
         ip_addresses.push(b.ip_address.parse()?);
@@ -51,6 +66,68 @@ pub fn discover() -> Result<Vec<IpAddr>> {
     Ok(ip_addresses)
 }
 
+/// A bridge that was found by [`discover_bridges`].
+///
+/// [`discover_bridges`]: fn.discover_bridges.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscoveredBridge {
+    /// Identifier of the bridge.
+    ///
+    /// This is the common name of the certificate of the bridge, see [`CertificateVerifier`].
+    ///
+    /// [`CertificateVerifier`]: ../tls/struct.CertificateVerifier.html
+    pub id: String,
+    /// IP address of the bridge.
+    pub ip_address: IpAddr,
+}
+
+/// Discovers bridges in the local network and returns their identifiers and IP addresses.
+///
+/// This sends the same request as [`discover`] with the [default timeout].
+///
+/// [`discover`]: fn.discover.html
+/// [default timeout]: constant.DEFAULT_TIMEOUT.html
+///
+/// # Examples
+///
+/// Connect to the first discovered bridge over HTTPS.
+/// ```no_run
+/// use huelib::tls::CertificateVerifier;
+/// use huelib::{bridge, Bridge};
+///
+/// let discovered = bridge::discover_bridges().unwrap().remove(0);
+/// let verifier = CertificateVerifier::new().bridge_id(discovered.id);
+/// let bridge = Bridge::new(discovered.ip_address, "username").https(verifier);
+/// ```
+pub fn discover_bridges() -> Result<Vec<DiscoveredBridge>> {
+    discover_bridges_with_timeout(DEFAULT_TIMEOUT)
+}
+
+/// Discovers bridges in the local network with a connect and read timeout.
+///
+/// See [`discover_bridges`] for more information.
+///
+/// [`discover_bridges`]: fn.discover_bridges.html
+pub fn discover_bridges_with_timeout(timeout: Duration) -> Result<Vec<DiscoveredBridge>> {
+    let timeout = Some(timeout);
+    let http_response = send(ureq::get(DISCOVERY_URL), None, timeout, timeout)?;
+    #[derive(Deserialize)]
+    struct BridgeJson {
+        id: String,
+        #[serde(rename = "internalipaddress")]
+        ip_address: String,
+    }
+    let bridges: Vec<BridgeJson> = serde_json::from_value(http_response)?;
+    let mut discovered = Vec::new();
+    for b in bridges {
+        discovered.push(DiscoveredBridge {
+            id: b.id,
+            ip_address: b.ip_address.parse()?,
+        });
+    }
+    Ok(discovered)
+}
+
 /// A user on a bridge.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
 pub struct User {
@@ -66,7 +143,10 @@ pub struct User {
 /// This will send a HTTP POST request with `devicetype` and `generate_clientkey` as body to the
 /// bridge with the specified IP address. The value of `devicetype` usally contains the app and
 /// device name. If `generate_clientkey` is set to true the returned user will contain a random
-/// generated 16 byte clientkey encoded as ASCII string of length 32.
+/// generated 16 byte clientkey encoded as ASCII string of length 32. The request is sent with the
+/// [default timeout].
+///
+/// [default timeout]: constant.DEFAULT_TIMEOUT.html
 ///
 /// # Examples
 ///
@@ -94,45 +174,320 @@ pub struct User {
 ///     Err(Error::ParseJson(_)) => eprintln!("Failed to parse json content"),
 ///     Err(Error::Response(e)) => eprintln!("Error from the Philips Hue API: {}", e),
 ///     Err(Error::GetUsername) => eprintln!("Failed to get the username"),
-///     Err(_) => unreachable!()
+///     Err(Error::Timeout) => eprintln!("The bridge did not respond in time"),
+///     Err(Error::Transport(e)) => eprintln!("Failed to connect to the bridge: {}", e),
+///     Err(e) => eprintln!("Failed to register user: {}", e),
 /// };
 /// ```
 pub fn register_user(
     ip_address: IpAddr,
     devicetype: impl AsRef<str>,
     generate_clientkey: bool,
+) -> Result<User> {
+    register_user_with_timeout(ip_address, devicetype, generate_clientkey, DEFAULT_TIMEOUT)
+}
+
+/// Registers a new user on a bridge with a connect and read timeout.
+///
+/// See [`register_user`] for more information.
+///
+/// [`register_user`]: fn.register_user.html
+pub fn register_user_with_timeout(
+    ip_address: IpAddr,
+    devicetype: impl AsRef<str>,
+    generate_clientkey: bool,
+    timeout: Duration,
 ) -> Result<User> {
     let url = format!("http://{}/api", ip_address);
-    let body = if generate_clientkey {
-        format!(
-            "{{\"devicetype\": \"{}\", \"generateclientkey\": true}}",
-            devicetype.as_ref()
-        )
-    } else {
-        format!("{{\"devicetype\": \"{}\"}}", devicetype.as_ref())
-    };
-    let http_response = ureq::post(&url).send_string(&body);
-    let mut responses: Vec<Response<User>> = serde_json::from_value(http_response.into_json()?)?;
+    let mut body = serde_json::json!({ "devicetype": devicetype.as_ref() });
+    if generate_clientkey {
+        body["generateclientkey"] = true.into();
+    }
+    let timeout = Some(timeout);
+    let http_response = send(ureq::post(&url), Some(&body), timeout, timeout)?;
+    let mut responses: Vec<Response<User>> = serde_json::from_value(http_response)?;
     match responses.pop() {
         Some(v) => v.into_result().map_err(Error::Response),
         None => Err(Error::GetUsername),
     }
 }
 
-enum RequestType {
+pub(crate) enum RequestType {
     Put(JsonValue),
     Post(JsonValue),
     Get,
     Delete,
 }
 
-fn parse_response<T: DeserializeOwned>(response: JsonValue) -> Result<T> {
-    if let Ok(mut v) = serde_json::from_value::<Vec<Response<JsonValue>>>(response.clone()) {
-        if let Some(v) = v.pop() {
-            v.into_result()?;
+impl RequestType {
+    /// Whether sending the request multiple times has the same effect as sending it once.
+    ///
+    /// Modifications that increment or decrement an attribute are not idempotent.
+    fn is_idempotent(&self) -> bool {
+        match self {
+            Self::Post(_) => false,
+            Self::Put(v) => !v
+                .as_object()
+                .is_some_and(|v| v.keys().any(|k| k.ends_with("_inc"))),
+            Self::Get | Self::Delete => true,
+        }
+    }
+
+    fn method(&self) -> Method {
+        match self {
+            Self::Put(_) => Method::Put,
+            Self::Post(_) => Method::Post,
+            Self::Get => Method::Get,
+            Self::Delete => Method::Delete,
+        }
+    }
+}
+
+/// Method of a HTTP request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Method {
+    /// HTTP GET request.
+    Get,
+    /// HTTP PUT request.
+    Put,
+    /// HTTP POST request.
+    Post,
+    /// HTTP DELETE request.
+    Delete,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Get => "GET",
+            Self::Put => "PUT",
+            Self::Post => "POST",
+            Self::Delete => "DELETE",
+        })
+    }
+}
+
+/// Context of a request to the Philips Hue API.
+///
+/// Errors of requests that are sent by a [`Bridge`] contain the context of the request, see
+/// [`Error::context`].
+///
+/// [`Bridge`]: struct.Bridge.html
+/// [`Error::context`]: ../enum.Error.html#method.context
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestContext {
+    /// Method of the request.
+    pub method: Method,
+    /// Path of the request relative to the API url, for example `lights/1/state`.
+    pub path: String,
+    /// Identifier of the resource that the request was sent to.
+    pub resource_id: Option<String>,
+}
+
+impl RequestContext {
+    fn new(path: &str, request_type: &RequestType) -> Self {
+        let address: response::Address = path.parse().unwrap_or_else(|e| match e {});
+        Self {
+            method: request_type.method(),
+            path: path.to_owned(),
+            // Scans for new lights and sensors are requested with the path `<kind>/new`.
+            resource_id: address.id.filter(|v| v != "new"),
+        }
+    }
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} /{}", self.method, self.path)
+    }
+}
+
+/// Sends a request with timeouts and reads the JSON body of the response.
+fn send(
+    mut request: ureq::Request,
+    body: Option<&JsonValue>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+) -> Result<JsonValue> {
+    if let Some(v) = connect_timeout {
+        request.timeout_connect(v.as_millis() as u64);
+    }
+    if let Some(v) = read_timeout {
+        request.timeout_read(v.as_millis() as u64);
+    }
+    let started = Instant::now();
+    let response = match body {
+        Some(v) => request.send_json(v.clone()),
+        None => request.call(),
+    };
+    // Failed connections do not contain the kind of the error, so a connection that failed after
+    // the connect timeout has passed is treated as timed out.
+    let connect_timed_out = connect_timeout.is_some_and(|v| started.elapsed() >= v);
+    into_json(response, connect_timed_out)
+}
+
+/// Reads the JSON body of a http response.
+///
+/// Transport errors and timeouts are returned as `Error::Transport` and `Error::Timeout`.
+/// Responses with a status other than 2xx or a body that is not valid JSON are returned as
+/// `Error::UnexpectedResponse`.
+fn into_json(response: ureq::Response, connect_timed_out: bool) -> Result<JsonValue> {
+    if let Some(e) = response.synthetic_error() {
+        return Err(match e {
+            ureq::Error::Io(v) if is_timeout(v.kind()) => Error::Timeout,
+            ureq::Error::ConnectionFailed(_) if connect_timed_out => Error::Timeout,
+            _ => Error::Transport(e.to_string()),
+        });
+    }
+    let status = response.status();
+    let body = response.into_string().map_err(|e| {
+        if is_timeout(e.kind()) {
+            Error::Timeout
+        } else {
+            Error::ParseHttpResponse(e)
+        }
+    })?;
+    match serde_json::from_str(&body) {
+        Ok(v) if (200..300).contains(&status) => Ok(v),
+        _ => Err(Error::UnexpectedResponse {
+            status,
+            body: excerpt(&body),
+        }),
+    }
+}
+
+/// Returns the beginning of a response body for error messages.
+fn excerpt(body: &str) -> String {
+    const MAX_LENGTH: usize = 256;
+    let body = body.trim();
+    match body.char_indices().nth(MAX_LENGTH) {
+        Some((i, _)) => format!("{}...", &body[..i]),
+        None => body.to_owned(),
+    }
+}
+
+fn is_timeout(kind: io::ErrorKind) -> bool {
+    kind == io::ErrorKind::TimedOut || kind == io::ErrorKind::WouldBlock
+}
+
+/// Whether every item of a response from the API is an internal error of the bridge.
+///
+/// Responses that also contain other items were partially applied and are not retried.
+fn is_internal_error(response: &JsonValue) -> bool {
+    let code = response::ErrorKind::InternalError as u16;
+    response.as_array().is_some_and(|v| {
+        !v.is_empty()
+            && v.iter()
+                .all(|v| v["error"]["type"].as_u64() == Some(code as u64))
+    })
+}
+
+/// Policy for retrying requests that failed because of a transient error.
+///
+/// Requests other than POST are retried if the bridge only returns internal errors. Requests that
+/// are safe to repeat (GET, DELETE and PUT without increments) are also retried if the request
+/// failed with a transport error or timed out.
+/// The delay between attempts starts with the initial backoff and doubles after every attempt,
+/// up to the maximal backoff.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximal number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Maximal delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy with the given number of retries.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the delay before the first retry and the maximal delay between two attempts.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Returns the delay before the given retry, starting with 0.
+    fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry);
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Returns a policy with 3 retries and a backoff from 100 milliseconds to 2 seconds.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Returns the first error if a response from the API only contains errors.
+///
+/// Most endpoints return an object if the request succeeded and an array of errors if it
+/// failed, while modifications return an array that can contain both successes and errors.
+/// Responses of PUT requests are modifications, so they are never collapsed into a single error
+/// and every error is kept in [`Modification::failed`].
+///
+/// [`Modification::failed`]: ../response/struct.Modification.html#structfield.failed
+fn response_error(response: &JsonValue, request_type: &RequestType) -> Option<response::Error> {
+    if let RequestType::Put(_) = request_type {
+        return None;
+    }
+    let is_error = |v: &JsonValue| {
+        v.as_object()
+            .is_some_and(|v| v.len() == 1 && v.contains_key("error"))
+    };
+    let first = match response {
+        JsonValue::Array(v) if !v.is_empty() && v.iter().all(is_error) => &v[0],
+        v if is_error(v) => v,
+        _ => return None,
+    };
+    serde_json::from_value(first["error"].clone()).ok()
+}
+
+/// Resources of a collection where resources that cannot be deserialized are skipped.
+///
+/// This is returned by methods like [`Bridge::get_all_lights_lenient`], so that a single
+/// resource that is not supported by this library does not fail the whole request.
+///
+/// [`Bridge::get_all_lights_lenient`]: struct.Bridge.html#method.get_all_lights_lenient
+#[derive(Debug)]
+pub struct LenientCollection<T> {
+    /// Resources that were deserialized.
+    pub resources: Vec<T>,
+    /// Identifiers and errors of resources that could not be deserialized.
+    pub errors: Vec<(String, Error)>,
+}
+
+impl<T: DeserializeOwned> LenientCollection<T> {
+    fn from_map(map: HashMap<String, JsonValue>, with_id: impl Fn(T, String) -> T) -> Self {
+        let mut collection = Self {
+            resources: Vec::new(),
+            errors: Vec::new(),
+        };
+        for (id, value) in map {
+            match serde_json::from_value(value) {
+                Ok(v) => collection.resources.push(with_id(v, id)),
+                Err(e) => collection.errors.push((id, Error::ParseJson(e))),
+            }
         }
+        collection
     }
-    Ok(serde_json::from_value(response)?)
 }
 
 /// A bridge with IP address and username.
@@ -144,6 +499,16 @@ pub struct Bridge {
     pub ip_address: IpAddr,
     /// Url to the Philips Hue API.
     api_url: String,
+    /// Scheduler that limits the rate of requests.
+    scheduler: Option<Scheduler>,
+    /// Timeout for connecting to the bridge.
+    connect_timeout: Option<Duration>,
+    /// Timeout for reading responses from the bridge.
+    read_timeout: Option<Duration>,
+    /// Policy for retrying failed requests.
+    retry_policy: Option<RetryPolicy>,
+    /// Connection settings for HTTPS requests.
+    connection: Option<tls::Connection>,
 }
 
 impl Bridge {
@@ -165,46 +530,256 @@ impl Bridge {
             api_url: format!("http://{}/api/{}", ip_address, &username),
             username,
             ip_address,
+            scheduler: None,
+            connect_timeout: None,
+            read_timeout: None,
+            retry_policy: None,
+            connection: None,
         }
     }
 
+    /// Sends requests over HTTPS and validates the certificate of the bridge.
+    ///
+    /// See [`CertificateVerifier`] for more information. Requests fail with a transport error if
+    /// the certificate is rejected, which can be checked with [`Error::is_transport`].
+    ///
+    /// [`CertificateVerifier`]: ../tls/struct.CertificateVerifier.html
+    /// [`Error::is_transport`]: ../enum.Error.html#method.is_transport
+    ///
+    /// # Examples
+    ///
+    /// Pin the bridge id from discovery and store the fingerprint of the first certificate for
+    /// later connections.
+    /// ```no_run
+    /// use huelib::tls::CertificateVerifier;
+    /// use huelib::{bridge, Bridge};
+    ///
+    /// let discovered = bridge::discover_bridges().unwrap().remove(0);
+    /// let verifier = CertificateVerifier::new()
+    ///     .bridge_id(discovered.id)
+    ///     .trust_on_first_use(true);
+    /// let bridge = Bridge::new(discovered.ip_address, "username").https(verifier.clone());
+    /// bridge.get_config().unwrap();
+    /// println!("Fingerprint: {}", verifier.pinned_fingerprint().unwrap());
+    /// ```
+    ///
+    /// Trust the first certificate and store its fingerprint for later connections.
+    /// ```no_run
+    /// use huelib::tls::CertificateVerifier;
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let verifier = CertificateVerifier::new().trust_on_first_use(true);
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username")
+    ///     .https(verifier.clone());
+    /// let lights = bridge.get_all_lights().unwrap();
+    /// println!("Fingerprint: {}", verifier.pinned_fingerprint().unwrap());
+    /// ```
+    pub fn https(mut self, verifier: CertificateVerifier) -> Self {
+        self.api_url = format!("https://{}/api/{}", tls::HOST_NAME, &self.username);
+        self.connection = Some(tls::Connection::new(self.ip_address, 443, verifier));
+        self
+    }
+
+    /// Sets the timeout for connecting to the bridge.
+    ///
+    /// If the timeout is exceeded, requests fail with an `Error::Request` that wraps
+    /// `Error::Timeout` together with the context of the request. Use [`Error::is_timeout`] to
+    /// check for it.
+    ///
+    /// [`Error::is_timeout`]: ../enum.Error.html#method.is_timeout
+    pub fn connect_timeout(mut self, value: Duration) -> Self {
+        self.connect_timeout = Some(value);
+        self
+    }
+
+    /// Sets the timeout for reading responses from the bridge.
+    ///
+    /// If the timeout is exceeded, requests fail like with the [connect timeout].
+    ///
+    /// [connect timeout]: #method.connect_timeout
+    pub fn read_timeout(mut self, value: Duration) -> Self {
+        self.read_timeout = Some(value);
+        self
+    }
+
+    /// Retries requests that failed because of a transient error.
+    ///
+    /// See [`RetryPolicy`] for more information.
+    ///
+    /// [`RetryPolicy`]: struct.RetryPolicy.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib::bridge::RetryPolicy;
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use std::time::Duration;
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username")
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .read_timeout(Duration::from_secs(5))
+    ///     .retry_policy(RetryPolicy::new(5));
+    /// ```
+    pub fn retry_policy(mut self, value: RetryPolicy) -> Self {
+        self.retry_policy = Some(value);
+        self
+    }
+
+    /// Limits the rate of requests with a scheduler.
+    ///
+    /// See [`Scheduler`] for more information.
+    ///
+    /// [`Scheduler`]: ../scheduler/struct.Scheduler.html
+    pub fn scheduler(mut self, value: Scheduler) -> Self {
+        self.scheduler = Some(value);
+        self
+    }
+
+    /// Returns whether the rate of requests is limited by the given scheduler.
+    pub(crate) fn uses_scheduler(&self, scheduler: &Scheduler) -> bool {
+        self.scheduler.as_ref() == Some(scheduler)
+    }
+
     /// Sends a HTTP request to the Philips Hue API and returns the response.
-    fn api_request<T: DeserializeOwned>(
+    pub(crate) fn api_request<T: DeserializeOwned>(
         &self,
         url_suffix: impl AsRef<str>,
         request_type: RequestType,
     ) -> Result<T> {
-        let url = format!("{}/{}", self.api_url, url_suffix.as_ref());
-        let response = match request_type {
-            RequestType::Put(v) => ureq::put(&url).send_json(v),
-            RequestType::Post(v) => ureq::post(&url).send_json(v),
-            RequestType::Get => ureq::get(&url).call(),
-            RequestType::Delete => ureq::delete(&url).call(),
+        let url = match url_suffix.as_ref() {
+            "" => self.api_url.clone(),
+            v => format!("{}/{}", self.api_url, v),
         };
-        Ok(serde_json::from_value(response.into_json()?)?)
+        self.send_with_retries(&url, url_suffix.as_ref(), &request_type)
+            .and_then(|v| Ok(serde_json::from_value(v)?))
+            .map_err(|e| Error::Request {
+                context: RequestContext::new(url_suffix.as_ref(), &request_type),
+                source: Box::new(e),
+            })
     }
 
-    /// Modifies the configuration of the bridge
-    pub fn set_config(
+    /// Sends a HTTP request and retries it according to the retry policy of the bridge.
+    fn send_with_retries(
         &self,
-        modifier: &resource::config::Modifier,
-    ) -> Result<Vec<ResponseModified>> {
+        url: &str,
+        url_suffix: &str,
+        request_type: &RequestType,
+    ) -> Result<JsonValue> {
+        let kind = CommandKind::from_request(url_suffix, request_type);
+        let max_retries = self.retry_policy.map_or(0, |v| v.max_retries);
+        let mut retry = 0;
+        loop {
+            if let Some(scheduler) = &self.scheduler {
+                scheduler.wait(kind);
+            }
+            let response = self.send_request(url, request_type);
+            let is_transient = match &response {
+                Err(Error::Transport(_)) | Err(Error::Timeout) => request_type.is_idempotent(),
+                Ok(v) => !matches!(request_type, RequestType::Post(_)) && is_internal_error(v),
+                Err(_) => false,
+            };
+            match self.retry_policy {
+                Some(policy) if is_transient && retry < max_retries => {
+                    thread::sleep(policy.delay(retry));
+                    retry += 1;
+                }
+                _ => {
+                    let response = response?;
+                    return match response_error(&response, request_type) {
+                        Some(e) => Err(Error::Response(e)),
+                        None => Ok(response),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Sends a single HTTP request with the timeouts of the bridge.
+    fn send_request(&self, url: &str, request_type: &RequestType) -> Result<JsonValue> {
+        let mut request = match (&self.connection, request_type) {
+            (Some(c), RequestType::Put(_)) => c.agent.put(url),
+            (Some(c), RequestType::Post(_)) => c.agent.post(url),
+            (Some(c), RequestType::Get) => c.agent.get(url),
+            (Some(c), RequestType::Delete) => c.agent.delete(url),
+            (None, RequestType::Put(_)) => ureq::put(url),
+            (None, RequestType::Post(_)) => ureq::post(url),
+            (None, RequestType::Get) => ureq::get(url),
+            (None, RequestType::Delete) => ureq::delete(url),
+        };
+        if let Some(v) = &self.connection {
+            request.set_tls_config(v.config.clone());
+        }
+        let body = match request_type {
+            RequestType::Put(v) | RequestType::Post(v) => Some(v),
+            RequestType::Get | RequestType::Delete => None,
+        };
+        send(request, body, self.connect_timeout, self.read_timeout)
+    }
+
+    /// Modifies the configuration of the bridge
+    pub fn set_config(&self, modifier: &resource::config::Modifier) -> Result<Modification> {
         self.api_request("config", RequestType::Put(serde_json::to_value(modifier)?))
     }
 
     /// Returns the configuration of the bridge.
     pub fn get_config(&self) -> Result<resource::Config> {
-        parse_response(self.api_request("config", RequestType::Get)?)
+        self.api_request("config", RequestType::Get)
+    }
+
+    /// Returns the full state of the bridge containing all resources.
+    pub fn get_datastore(&self) -> Result<resource::Datastore> {
+        self.api_request("", RequestType::Get)
+    }
+
+    /// Writes the configuration of the bridge to a versioned JSON archive.
+    ///
+    /// The archive contains groups, scenes with their light states, schedules, rules, the
+    /// configuration of sensors and resourcelinks. See [`backup::Archive`] for more information.
+    ///
+    /// [`backup::Archive`]: ../backup/struct.Archive.html
+    ///
+    /// # Examples
+    ///
+    /// Export the configuration of a bridge to a file and import it on another bridge.
+    /// ```no_run
+    /// use huelib::Bridge;
+    /// use std::fs::File;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// bridge.export(File::create("backup.json").unwrap()).unwrap();
+    ///
+    /// let other = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 3)), "username");
+    /// let report = other.import(File::open("backup.json").unwrap()).unwrap();
+    /// println!("Created {} resources", report.created.len());
+    /// ```
+    pub fn export(&self, writer: impl io::Write) -> Result<()> {
+        let archive = backup::Archive::export(self)?;
+        Ok(serde_json::to_writer_pretty(writer, &archive)?)
+    }
+
+    /// Recreates the configuration of an archive that was written by [`export`].
+    ///
+    /// Lights and sensors are mapped by their unique identifier and the addresses in rules and
+    /// schedules are rewritten. See [`backup::Archive::import`] for more information.
+    ///
+    /// [`export`]: #method.export
+    /// [`backup::Archive::import`]: ../backup/struct.Archive.html#method.import
+    pub fn import(&self, reader: impl io::Read) -> Result<backup::ImportReport> {
+        let archive: backup::Archive = serde_json::from_reader(reader)?;
+        archive.import(self)
     }
 
     /// Modifies attributes of a light.
     pub fn set_light_attribute(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::LightId>,
         modifier: &resource::light::AttributeModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("lights/{}", id.as_ref()),
+            format!("lights/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
@@ -212,27 +787,26 @@ impl Bridge {
     /// Modifies the state of a light.
     pub fn set_light_state(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::LightId>,
         modifier: &resource::light::StateModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("lights/{}/state", id.as_ref()),
+            format!("lights/{}/state", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a light.
-    pub fn get_light(&self, id: impl AsRef<str>) -> Result<resource::Light> {
-        let light: resource::Light = parse_response(
-            self.api_request(&format!("lights/{}", id.as_ref()), RequestType::Get)?,
-        )?;
-        Ok(light.with_id(id.as_ref()))
+    pub fn get_light(&self, id: impl Into<resource::LightId>) -> Result<resource::Light> {
+        let id = id.into();
+        let light: resource::Light =
+            self.api_request(format!("lights/{}", id), RequestType::Get)?;
+        Ok(light.with_id(id))
     }
 
     /// Returns all lights that are connected to the bridge.
     pub fn get_all_lights(&self) -> Result<Vec<resource::Light>> {
-        let map: HashMap<String, resource::Light> =
-            parse_response(self.api_request("lights", RequestType::Get)?)?;
+        let map: HashMap<String, resource::Light> = self.api_request("lights", RequestType::Get)?;
         let mut lights = Vec::new();
         for (id, light) in map {
             lights.push(light.with_id(id));
@@ -240,6 +814,27 @@ impl Bridge {
         Ok(lights)
     }
 
+    /// Returns all lights that are connected to the bridge and skips lights that cannot be
+    /// deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let lights = bridge.get_all_lights_lenient().unwrap();
+    /// for (id, error) in &lights.errors {
+    ///     eprintln!("Skipped light {}: {}", id, error);
+    /// }
+    /// println!("{:?}", lights.resources);
+    /// ```
+    pub fn get_all_lights_lenient(&self) -> Result<LenientCollection<resource::Light>> {
+        let map = self.api_request("lights", RequestType::Get)?;
+        Ok(LenientCollection::from_map(map, resource::Light::with_id))
+    }
+
     /// Starts searching for new lights.
     ///
     /// The bridge will open the network for 40 seconds. The overall search might take longer since
@@ -267,13 +862,13 @@ impl Bridge {
 
     /// Returns discovered lights.
     pub fn get_new_lights(&self) -> Result<resource::Scan> {
-        parse_response(self.api_request("lights/new", RequestType::Get)?)
+        self.api_request("lights/new", RequestType::Get)
     }
 
     /// Deletes a light from the bridge.
-    pub fn delete_light(&self, id: impl AsRef<str>) -> Result<()> {
+    pub fn delete_light(&self, id: impl Into<resource::LightId>) -> Result<()> {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("lights/{}", id.as_ref()), RequestType::Delete)?;
+            self.api_request(format!("lights/{}", id.into()), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
@@ -281,12 +876,12 @@ impl Bridge {
     }
 
     /// Creates a new group.
-    pub fn create_group(&self, creator: &resource::group::Creator) -> Result<String> {
+    pub fn create_group(&self, creator: &resource::group::Creator) -> Result<resource::GroupId> {
         let mut response: Vec<Response<HashMap<String, String>>> =
             self.api_request("groups", RequestType::Post(serde_json::to_value(creator)?))?;
         match response.pop() {
             Some(v) => match v.into_result()?.get("id") {
-                Some(v) => Ok(v.to_string()),
+                Some(v) => Ok(v.into()),
                 None => Err(Error::GetCreatedId),
             },
             None => Err(Error::GetCreatedId),
@@ -296,11 +891,11 @@ impl Bridge {
     /// Modifies attributes of a group.
     pub fn set_group_attribute(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::GroupId>,
         modifier: &resource::group::AttributeModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("groups/{}", id.as_ref()),
+            format!("groups/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
@@ -308,27 +903,26 @@ impl Bridge {
     /// Modifies the state of a group.
     pub fn set_group_state(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::GroupId>,
         modifier: &resource::group::StateModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("groups/{}/action", id.as_ref()),
+            format!("groups/{}/action", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a group.
-    pub fn get_group(&self, id: impl AsRef<str>) -> Result<resource::Group> {
-        let group: resource::Group = parse_response(
-            self.api_request(&format!("groups/{}", id.as_ref()), RequestType::Get)?,
-        )?;
-        Ok(group.with_id(id.as_ref()))
+    pub fn get_group(&self, id: impl Into<resource::GroupId>) -> Result<resource::Group> {
+        let id = id.into();
+        let group: resource::Group =
+            self.api_request(format!("groups/{}", id), RequestType::Get)?;
+        Ok(group.with_id(id))
     }
 
     /// Returns all groups.
     pub fn get_all_groups(&self) -> Result<Vec<resource::Group>> {
-        let map: HashMap<String, resource::Group> =
-            parse_response(self.api_request("groups", RequestType::Get)?)?;
+        let map: HashMap<String, resource::Group> = self.api_request("groups", RequestType::Get)?;
         let mut groups = Vec::new();
         for (id, group) in map {
             groups.push(group.with_id(id));
@@ -337,9 +931,9 @@ impl Bridge {
     }
 
     /// Deletes a group from the bridge.
-    pub fn delete_group(&self, id: impl AsRef<str>) -> Result<()> {
+    pub fn delete_group(&self, id: impl Into<resource::GroupId>) -> Result<()> {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("groups/{}", id.as_ref()), RequestType::Delete)?;
+            self.api_request(format!("groups/{}", id.into()), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
@@ -347,12 +941,12 @@ impl Bridge {
     }
 
     /// Creates a new scene.
-    pub fn create_scene(&self, creator: &resource::scene::Creator) -> Result<String> {
+    pub fn create_scene(&self, creator: &resource::scene::Creator) -> Result<resource::SceneId> {
         let mut response: Vec<Response<HashMap<String, String>>> =
             self.api_request("scenes", RequestType::Post(serde_json::to_value(creator)?))?;
         match response.pop() {
             Some(v) => match v.into_result()?.get("id") {
-                Some(v) => Ok(v.to_string()),
+                Some(v) => Ok(v.into()),
                 None => Err(Error::GetCreatedId),
             },
             None => Err(Error::GetCreatedId),
@@ -362,27 +956,26 @@ impl Bridge {
     /// Modifies the state and attributes of a scene.
     pub fn set_scene(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::SceneId>,
         modifier: &resource::scene::Modifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("scenes/{}", id.as_ref()),
+            format!("scenes/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a scene.
-    pub fn get_scene(&self, id: impl AsRef<str>) -> Result<resource::Scene> {
-        let scene: resource::Scene = parse_response(
-            self.api_request(&format!("scenes/{}", id.as_ref()), RequestType::Get)?,
-        )?;
-        Ok(scene.with_id(id.as_ref()))
+    pub fn get_scene(&self, id: impl Into<resource::SceneId>) -> Result<resource::Scene> {
+        let id = id.into();
+        let scene: resource::Scene =
+            self.api_request(format!("scenes/{}", id), RequestType::Get)?;
+        Ok(scene.with_id(id))
     }
 
     /// Returns all scenes.
     pub fn get_all_scenes(&self) -> Result<Vec<resource::Scene>> {
-        let map: HashMap<String, resource::Scene> =
-            parse_response(self.api_request("scenes", RequestType::Get)?)?;
+        let map: HashMap<String, resource::Scene> = self.api_request("scenes", RequestType::Get)?;
         let mut scenes = Vec::new();
         for (id, scene) in map {
             scenes.push(scene.with_id(id));
@@ -391,9 +984,9 @@ impl Bridge {
     }
 
     /// Deletes a scene.
-    pub fn delete_scene(&self, id: impl AsRef<str>) -> Result<()> {
+    pub fn delete_scene(&self, id: impl Into<resource::SceneId>) -> Result<()> {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("scenes/{}", id.as_ref()), RequestType::Delete)?;
+            self.api_request(format!("scenes/{}", id.into()), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
@@ -402,18 +995,21 @@ impl Bridge {
 
     /// Returns the capabilities of resources.
     pub fn get_capabilities(&self) -> Result<resource::Capabilities> {
-        parse_response(self.api_request("capabilities", RequestType::Get)?)
+        self.api_request("capabilities", RequestType::Get)
     }
 
     /// Creates a new schedule and returns the identifier.
-    pub fn create_schedule(&self, creator: &resource::schedule::Creator) -> Result<String> {
+    pub fn create_schedule(
+        &self,
+        creator: &resource::schedule::Creator,
+    ) -> Result<resource::ScheduleId> {
         let mut response: Vec<Response<HashMap<String, String>>> = self.api_request(
             "schedules",
             RequestType::Post(serde_json::to_value(creator)?),
         )?;
         match response.pop() {
             Some(v) => match v.into_result()?.get("id") {
-                Some(v) => Ok(v.to_string()),
+                Some(v) => Ok(v.into()),
                 None => Err(Error::GetCreatedId),
             },
             None => Err(Error::GetCreatedId),
@@ -423,27 +1019,27 @@ impl Bridge {
     /// Modifies attributes of a schedule.
     pub fn set_schedule(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::ScheduleId>,
         modifier: &resource::schedule::Modifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("schedules/{}", id.as_ref()),
+            format!("schedules/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a schedule.
-    pub fn get_schedule(&self, id: impl AsRef<str>) -> Result<resource::Schedule> {
-        let schedule: resource::Schedule = parse_response(
-            self.api_request(&format!("schedules/{}", id.as_ref()), RequestType::Get)?,
-        )?;
-        Ok(schedule.with_id(id.as_ref()))
+    pub fn get_schedule(&self, id: impl Into<resource::ScheduleId>) -> Result<resource::Schedule> {
+        let id = id.into();
+        let schedule: resource::Schedule =
+            self.api_request(format!("schedules/{}", id), RequestType::Get)?;
+        Ok(schedule.with_id(id))
     }
 
     /// Returns all schedules.
     pub fn get_all_schedules(&self) -> Result<Vec<resource::Schedule>> {
         let map: HashMap<String, resource::Schedule> =
-            parse_response(self.api_request("schedules", RequestType::Get)?)?;
+            self.api_request("schedules", RequestType::Get)?;
         let mut schedules = Vec::new();
         for (id, schedule) in map {
             schedules.push(schedule.with_id(id));
@@ -452,9 +1048,9 @@ impl Bridge {
     }
 
     /// Deletes a schedule.
-    pub fn delete_schedule(&self, id: impl AsRef<str>) -> Result<()> {
+    pub fn delete_schedule(&self, id: impl Into<resource::ScheduleId>) -> Result<()> {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("schedules/{}", id.as_ref()), RequestType::Delete)?;
+            self.api_request(format!("schedules/{}", id.into()), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
@@ -462,14 +1058,17 @@ impl Bridge {
     }
 
     /// Creates a new resourcelink and returns the identifier.
-    pub fn create_resourcelink(&self, creator: &resource::resourcelink::Creator) -> Result<String> {
+    pub fn create_resourcelink(
+        &self,
+        creator: &resource::resourcelink::Creator,
+    ) -> Result<resource::ResourcelinkId> {
         let mut response: Vec<Response<HashMap<String, String>>> = self.api_request(
             "resourcelinks",
             RequestType::Post(serde_json::to_value(creator)?),
         )?;
         match response.pop() {
             Some(v) => match v.into_result()?.get("id") {
-                Some(v) => Ok(v.to_string()),
+                Some(v) => Ok(v.into()),
                 None => Err(Error::GetCreatedId),
             },
             None => Err(Error::GetCreatedId),
@@ -479,27 +1078,30 @@ impl Bridge {
     /// Modifies attributes of a resourcelink.
     pub fn set_resourcelink(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::ResourcelinkId>,
         modifier: &resource::resourcelink::Modifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("resourcelinks/{}", id.as_ref()),
+            format!("resourcelinks/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a resourcelink.
-    pub fn get_resourcelink(&self, id: impl AsRef<str>) -> Result<resource::Resourcelink> {
-        let resourcelink: resource::Resourcelink = parse_response(
-            self.api_request(&format!("resourcelinks/{}", id.as_ref()), RequestType::Get)?,
-        )?;
-        Ok(resourcelink.with_id(id.as_ref()))
+    pub fn get_resourcelink(
+        &self,
+        id: impl Into<resource::ResourcelinkId>,
+    ) -> Result<resource::Resourcelink> {
+        let id = id.into();
+        let resourcelink: resource::Resourcelink =
+            self.api_request(format!("resourcelinks/{}", id), RequestType::Get)?;
+        Ok(resourcelink.with_id(id))
     }
 
     /// Returns all resourcelinks.
     pub fn get_all_resourcelinks(&self) -> Result<Vec<resource::Resourcelink>> {
         let map: HashMap<String, resource::Resourcelink> =
-            parse_response(self.api_request("resourcelinks", RequestType::Get)?)?;
+            self.api_request("resourcelinks", RequestType::Get)?;
         let mut resourcelinks = Vec::new();
         for (id, resourcelink) in map {
             resourcelinks.push(resourcelink.with_id(id));
@@ -508,11 +1110,9 @@ impl Bridge {
     }
 
     /// Deletes a resourcelink.
-    pub fn delete_resourcelink(&self, id: impl AsRef<str>) -> Result<()> {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("resourcelinks/{}", id.as_ref()),
-            RequestType::Delete,
-        )?;
+    pub fn delete_resourcelink(&self, id: impl Into<resource::ResourcelinkId>) -> Result<()> {
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("resourcelinks/{}", id.into()), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
@@ -522,11 +1122,11 @@ impl Bridge {
     /// Modifies attributes of a sensor.
     pub fn set_sensor_attribute(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::SensorId>,
         modifier: &resource::sensor::AttributeModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("sensors/{}", id.as_ref()),
+            format!("sensors/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
@@ -534,11 +1134,11 @@ impl Bridge {
     /// Modifies the state of a sensor.
     pub fn set_sensor_state(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::SensorId>,
         modifier: &resource::sensor::StateModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("sensors/{}/state", id.as_ref()),
+            format!("sensors/{}/state", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
@@ -546,27 +1146,27 @@ impl Bridge {
     /// Modifies the configuration of a sensor.
     pub fn set_sensor_config(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::SensorId>,
         modifier: &resource::sensor::ConfigModifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("sensors/{}/config", id.as_ref()),
+            format!("sensors/{}/config", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a sensor.
-    pub fn get_sensor(&self, id: impl AsRef<str>) -> Result<resource::Sensor> {
-        let sensor: resource::Sensor = parse_response(
-            self.api_request(&format!("sensors/{}", id.as_ref()), RequestType::Get)?,
-        )?;
-        Ok(sensor.with_id(id.as_ref()))
+    pub fn get_sensor(&self, id: impl Into<resource::SensorId>) -> Result<resource::Sensor> {
+        let id = id.into();
+        let sensor: resource::Sensor =
+            self.api_request(format!("sensors/{}", id), RequestType::Get)?;
+        Ok(sensor.with_id(id))
     }
 
     /// Returns all sensors that are connected to the bridge.
     pub fn get_all_sensors(&self) -> Result<Vec<resource::Sensor>> {
         let map: HashMap<String, resource::Sensor> =
-            parse_response(self.api_request("sensors", RequestType::Get)?)?;
+            self.api_request("sensors", RequestType::Get)?;
         let mut sensors = Vec::new();
         for (id, sensor) in map {
             sensors.push(sensor.with_id(id));
@@ -574,6 +1174,17 @@ impl Bridge {
         Ok(sensors)
     }
 
+    /// Returns all sensors that are connected to the bridge and skips sensors that cannot be
+    /// deserialized.
+    ///
+    /// See [`get_all_lights_lenient`] for more information.
+    ///
+    /// [`get_all_lights_lenient`]: #method.get_all_lights_lenient
+    pub fn get_all_sensors_lenient(&self) -> Result<LenientCollection<resource::Sensor>> {
+        let map = self.api_request("sensors", RequestType::Get)?;
+        Ok(LenientCollection::from_map(map, resource::Sensor::with_id))
+    }
+
     /// Starts searching for new sensors.
     ///
     /// The bridge will open the network for 40 seconds. The overall search might take longer since
@@ -601,13 +1212,13 @@ impl Bridge {
 
     /// Returns discovered sensors.
     pub fn get_new_sensors(&self) -> Result<resource::Scan> {
-        parse_response(self.api_request("sensors/new", RequestType::Get)?)
+        self.api_request("sensors/new", RequestType::Get)
     }
 
     /// Deletes a sensor from the bridge.
-    pub fn delete_sensor(&self, id: impl AsRef<str>) -> Result<()> {
+    pub fn delete_sensor(&self, id: impl Into<resource::SensorId>) -> Result<()> {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("sensors/{}", id.as_ref()), RequestType::Delete)?;
+            self.api_request(format!("sensors/{}", id.into()), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
@@ -615,12 +1226,17 @@ impl Bridge {
     }
 
     /// Creates a new rule.
-    pub fn create_rule(&self, creator: &resource::rule::Creator) -> Result<String> {
+    ///
+    /// Use [`rule::Creator::validate`] to check the creator against the limits of the bridge
+    /// before sending it.
+    ///
+    /// [`rule::Creator::validate`]: ../resource/rule/struct.Creator.html#method.validate
+    pub fn create_rule(&self, creator: &resource::rule::Creator) -> Result<resource::RuleId> {
         let mut response: Vec<Response<HashMap<String, String>>> =
             self.api_request("rules", RequestType::Post(serde_json::to_value(creator)?))?;
         match response.pop() {
             Some(v) => match v.into_result()?.get("id") {
-                Some(v) => Ok(v.to_string()),
+                Some(v) => Ok(v.into()),
                 None => Err(Error::GetCreatedId),
             },
             None => Err(Error::GetCreatedId),
@@ -630,26 +1246,25 @@ impl Bridge {
     /// Modifies attributes of a rule.
     pub fn set_rule(
         &self,
-        id: impl AsRef<str>,
+        id: impl Into<resource::RuleId>,
         modifier: &resource::rule::Modifier,
-    ) -> Result<Vec<ResponseModified>> {
+    ) -> Result<Modification> {
         self.api_request(
-            &format!("rules/{}", id.as_ref()),
+            format!("rules/{}", id.into()),
             RequestType::Put(serde_json::to_value(modifier)?),
         )
     }
 
     /// Returns a rule.
-    pub fn get_rule(&self, id: impl AsRef<str>) -> Result<resource::Rule> {
-        let rule: resource::Rule =
-            parse_response(self.api_request(&format!("rules/{}", id.as_ref()), RequestType::Get)?)?;
-        Ok(rule.with_id(id.as_ref()))
+    pub fn get_rule(&self, id: impl Into<resource::RuleId>) -> Result<resource::Rule> {
+        let id = id.into();
+        let rule: resource::Rule = self.api_request(format!("rules/{}", id), RequestType::Get)?;
+        Ok(rule.with_id(id))
     }
 
     /// Returns all rules.
     pub fn get_all_rules(&self) -> Result<Vec<resource::Rule>> {
-        let map: HashMap<String, resource::Rule> =
-            parse_response(self.api_request("rules", RequestType::Get)?)?;
+        let map: HashMap<String, resource::Rule> = self.api_request("rules", RequestType::Get)?;
         let mut rules = Vec::new();
         for (id, rule) in map {
             rules.push(rule.with_id(id));
@@ -658,12 +1273,38 @@ impl Bridge {
     }
 
     /// Deletes a rule.
-    pub fn delete_rule(&self, id: impl AsRef<str>) -> Result<()> {
+    pub fn delete_rule(&self, id: impl Into<resource::RuleId>) -> Result<()> {
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("rules/{}", id.into()), RequestType::Delete)?;
+        for i in response {
+            i.into_result()?;
+        }
+        Ok(())
+    }
+
+    /// Deletes a resource that is referenced by a link.
+    ///
+    /// Lights, sensors, groups, scenes, schedules, rules and resourcelinks can be deleted.
+    pub fn delete_resource(&self, link: &resource::resourcelink::Link) -> Result<()> {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("rules/{}", id.as_ref()), RequestType::Delete)?;
+            self.api_request(format!("{}/{}", link.kind, link.id), RequestType::Delete)?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
+
+    /// Deletes the resources of a plan in the given order.
+    ///
+    /// Resources that were already removed, for example because the bridge automatically deleted
+    /// a recycled resource, are skipped.
+    pub fn execute_delete_plan(&self, plan: &resource::datastore::DeletePlan) -> Result<()> {
+        for link in &plan.resources {
+            match self.delete_resource(link) {
+                Err(e) if e.is_not_found() => {}
+                v => v?,
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,166 @@
+use crate::resource::{light::ControlCapabilities, ModifierType};
+
+/// Brightness of a light.
+///
+/// The brightness is limited to the range that is accepted by the bridge, where 1 is the minimum
+/// and 254 the maximum brightness. Besides the raw value, the brightness can be expressed as a
+/// percentage or as a perceptual lightness, which makes equal steps look equally large.
+///
+/// # Examples
+///
+/// Dim a light by a perceptual step of 10 percent.
+/// ```no_run
+/// use huelib::resource::{light, Modifier};
+/// use huelib::{Bridge, Brightness};
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+/// let light = bridge.get_light("1").unwrap();
+/// let current = Brightness::new(light.state.brightness.unwrap_or(254));
+/// let (modifier_type, value) = current.perceptual_step(-10.0);
+/// let modifier = light::StateModifier::new().brightness(modifier_type, value);
+/// bridge.set_light_state("1", &modifier).unwrap();
+/// ```
+///
+/// Set the brightness of a light to half of its range.
+/// ```no_run
+/// use huelib::resource::{light, Modifier, ModifierType};
+/// use huelib::{Bridge, Brightness};
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+/// let brightness = Brightness::from_percent(50.0);
+/// let modifier = light::StateModifier::new().brightness(ModifierType::Override, brightness);
+/// bridge.set_light_state("1", &modifier).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub struct Brightness(u8);
+
+impl Brightness {
+    /// Minimal brightness.
+    pub const MIN: Self = Self(1);
+
+    /// Maximal brightness.
+    pub const MAX: Self = Self(254);
+
+    /// Creates a new brightness from a raw value.
+    ///
+    /// Values outside of the range from 1 to 254 are clamped.
+    pub fn new(value: u8) -> Self {
+        Self(value.clamp(Self::MIN.0, Self::MAX.0))
+    }
+
+    /// Returns the raw value of the brightness.
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Creates a new brightness from a percentage between 0 and 100.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Brightness;
+    /// assert_eq!(Brightness::from_percent(0.0), Brightness::MIN);
+    /// assert_eq!(Brightness::from_percent(50.0).value(), 128);
+    /// assert_eq!(Brightness::from_percent(150.0), Brightness::MAX);
+    /// ```
+    pub fn from_percent(percent: f32) -> Self {
+        let range = (Self::MAX.0 - Self::MIN.0) as f32;
+        let value = Self::MIN.0 as f32 + (percent / 100.0).clamp(0.0, 1.0) * range;
+        Self(value.round() as u8)
+    }
+
+    /// Returns the brightness as a percentage between 0 and 100.
+    pub fn percent(self) -> f32 {
+        (self.0 - Self::MIN.0) as f32 / (Self::MAX.0 - Self::MIN.0) as f32 * 100.0
+    }
+
+    /// Creates a new brightness from a perceptual lightness between 0 and 100.
+    ///
+    /// The lightness follows the CIE lightness curve, so that equal steps of lightness appear
+    /// as equal changes of brightness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Brightness;
+    /// let half = Brightness::from_lightness(50.0);
+    /// assert!(half < Brightness::from_percent(50.0));
+    /// assert!((half.lightness() - 50.0).abs() < 1.0);
+    /// ```
+    pub fn from_lightness(lightness: f32) -> Self {
+        let lightness = lightness.clamp(0.0, 100.0);
+        let luminance = if lightness > 8.0 {
+            ((lightness + 16.0) / 116.0).powi(3)
+        } else {
+            lightness / 903.3
+        };
+        Self::from_percent(luminance * 100.0)
+    }
+
+    /// Returns the perceptual lightness of the brightness between 0 and 100.
+    pub fn lightness(self) -> f32 {
+        let luminance = self.percent() / 100.0;
+        if luminance > 0.008_856 {
+            116.0 * luminance.cbrt() - 16.0
+        } else {
+            903.3 * luminance
+        }
+    }
+
+    /// Creates a new brightness from a percentage of the light output of a light.
+    ///
+    /// Lights can not be dimmed below their minimal dim level, which is reported in hundredths of
+    /// a percent. Percentages below the minimal dim level result in the minimal brightness, while
+    /// the remaining range is spread over all brightness values.
+    pub fn from_output_percent(percent: f32, capabilities: &ControlCapabilities) -> Self {
+        let min = capabilities
+            .min_dimlevel
+            .map_or(0.0, |v| (v as f32 / 100.0).clamp(0.0, 99.0));
+        Self::from_percent((percent - min) / (100.0 - min) * 100.0)
+    }
+
+    /// Returns the modifier type and value that change the brightness by a perceptual step.
+    ///
+    /// The step is given in lightness percent and can be negative to dim the light. A non-zero
+    /// step always changes the brightness unless it is already at its minimum or maximum.
+    /// The result can be used with the `brightness` methods of state modifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resource::ModifierType;
+    /// use huelib::Brightness;
+    ///
+    /// let (modifier_type, value) = Brightness::new(20).perceptual_step(10.0);
+    /// assert_eq!(modifier_type, ModifierType::Increment);
+    /// assert!(value > 0);
+    /// ```
+    pub fn perceptual_step(self, step: f32) -> (ModifierType, u8) {
+        let target = Self::from_lightness(self.lightness() + step);
+        let mut delta = target.0 as i16 - self.0 as i16;
+        if delta == 0 && step > 0.0 && self < Self::MAX {
+            delta = 1;
+        } else if delta == 0 && step < 0.0 && self > Self::MIN {
+            delta = -1;
+        }
+        if delta < 0 {
+            (ModifierType::Decrement, (-delta) as u8)
+        } else {
+            (ModifierType::Increment, delta as u8)
+        }
+    }
+}
+
+impl Default for Brightness {
+    fn default() -> Self {
+        Self::MAX
+    }
+}
+
+impl From<Brightness> for u8 {
+    fn from(value: Brightness) -> Self {
+        value.0
+    }
+}
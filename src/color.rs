@@ -1,4 +1,7 @@
+use crate::resource::{light, LightId, Modifier};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::num::ParseIntError;
+use std::str::FromStr;
 use thiserror::Error as ThisError;
 
 /// Struct for setting the color of a light.
@@ -41,18 +44,271 @@ impl Color {
         let z = red * 0.000_000 + green * 0.053_077 + blue * 1.035_763;
         Self {
             space_coordinates: (
-                x / (x + y + z + std::f32::MIN_POSITIVE),
-                y / (x + y + z + std::f32::MIN_POSITIVE),
+                x / (x + y + z + f32::MIN_POSITIVE),
+                y / (x + y + z + f32::MIN_POSITIVE),
             ),
             brightness: Some((y * 255.0) as u8),
         }
     }
 
+    /// Creates a new color from the hue and saturation of a light.
+    ///
+    /// The hue is between 0 and 65535 and the saturation between 0 and 254, like in
+    /// [`light::State`]. This only changes the color of a light and not the brightness.
+    ///
+    /// [`light::State`]: ../resource/light/struct.State.html
+    pub fn from_hue_saturation(hue: u16, saturation: u8) -> Self {
+        let (red, green, blue) = hsv_to_rgb(
+            hue as f32 / 65535.0 * 360.0,
+            saturation.min(254) as f32 / 254.0,
+            1.0,
+        );
+        Self {
+            brightness: None,
+            ..Self::from_rgb(red, green, blue)
+        }
+    }
+
+    /// Creates a new color from a mired color temperature.
+    ///
+    /// The color is approximated with the planckian locus. This only changes the color of a light
+    /// and not the brightness.
+    pub fn from_color_temperature(mired: u16) -> Self {
+        let (x, y) = planckian_locus(1_000_000.0 / mired.max(1) as f32);
+        Self::from_space_coordinates(x, y)
+    }
+
+    /// Creates a new color from a color temperature in kelvin.
+    ///
+    /// The color is approximated with the planckian locus, which allows lights without support
+    /// for color temperatures to show a white color. This only changes the color of a light and
+    /// not the brightness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Color;
+    /// let warm_white = Color::from_kelvin(2500);
+    /// assert_eq!(warm_white, Color::from_color_temperature(400));
+    /// ```
+    pub fn from_kelvin(kelvin: u16) -> Self {
+        let (x, y) = planckian_locus(kelvin as f32);
+        Self::from_space_coordinates(x, y)
+    }
+
+    /// Converts the color to rgb values.
+    ///
+    /// If the color has no brightness, the maximum brightness is used. The conversion is not
+    /// exact because the brightness of a color is stored as an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Color;
+    /// let (red, green, blue) = Color::from_rgb(255, 0, 0).to_rgb();
+    /// assert!(red >= 254 && green == 0 && blue == 0);
+    /// ```
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let (x, y) = self.space_coordinates;
+        if y <= 0.0 {
+            return (0, 0, 0);
+        }
+        let luminance = self.brightness.unwrap_or(u8::MAX) as f32 / 255.0;
+        // Inverse of the wide gamut matrix used by `from_rgb`.
+        let big_x = luminance / y * x;
+        let big_z = luminance / y * (1.0 - x - y);
+        let rgb = [
+            big_x * 1.611_757 - luminance * 0.202_805 - big_z * 0.302_298,
+            -big_x * 0.509_057 + luminance * 1.411_914 + big_z * 0.066_07,
+            big_x * 0.026_086 - luminance * 0.072_353 + big_z * 0.962_086,
+        ];
+        let normalize = |rgb: [f32; 3]| {
+            let max = rgb.iter().copied().fold(1.0, f32::max);
+            [rgb[0] / max, rgb[1] / max, rgb[2] / max]
+        };
+        let reverse_gamma = |v: f32| {
+            let v = v.max(0.0);
+            if v <= 0.003_130_8 {
+                12.92 * v
+            } else {
+                (1.0 + 0.055) * v.powf(1.0 / 2.4) - 0.055
+            }
+        };
+        let rgb = normalize(normalize(rgb).map(reverse_gamma));
+        let to_u8 = |v: f32| (v * 255.0).round() as u8;
+        (to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2]))
+    }
+
+    /// Converts the color to a hex string in the format `#RRGGBB`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Color;
+    /// let color = Color::from_hex("#00FF00").unwrap();
+    /// assert_eq!(color.to_hex(), "#00FF00");
+    /// ```
+    pub fn to_hex(&self) -> String {
+        let (red, green, blue) = self.to_rgb();
+        format!("#{:02X}{:02X}{:02X}", red, green, blue)
+    }
+
+    /// Converts the color to hue, saturation and value.
+    ///
+    /// The hue is in degrees between 0 and 360, the saturation and value are between 0 and 1.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (red, green, blue) = self.to_rgb();
+        let (red, green, blue) = (
+            red as f32 / 255.0,
+            green as f32 / 255.0,
+            blue as f32 / 255.0,
+        );
+        let max = red.max(green).max(blue);
+        let delta = max - red.min(green).min(blue);
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == red {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if max == green {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        (hue, saturation, max)
+    }
+
+    /// Interpolates between this color and another color.
+    ///
+    /// The factor is between 0 and 1, where 0 returns this color and 1 returns the other color.
+    /// The brightness is only interpolated if both colors have a brightness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::color::{Color, Interpolation};
+    ///
+    /// let red = Color::from_rgb(255, 0, 0);
+    /// let blue = Color::from_rgb(0, 0, 255);
+    /// assert_eq!(red.interpolate(&blue, 0.0, Interpolation::Oklab), red);
+    /// let purple = red.interpolate(&blue, 0.5, Interpolation::SpaceCoordinates);
+    /// assert!(purple.space_coordinates().0 < red.space_coordinates().0);
+    /// ```
+    pub fn interpolate(&self, other: &Color, factor: f32, interpolation: Interpolation) -> Self {
+        let factor = factor.clamp(0.0, 1.0);
+        if factor == 0.0 {
+            return *self;
+        } else if factor == 1.0 {
+            return *other;
+        }
+        let lerp = |a: f64, b: f64| a + (b - a) * factor as f64;
+        let lerp3 =
+            |a: [f64; 3], b: [f64; 3]| [lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])];
+        let has_brightness = self.brightness.is_some() && other.brightness.is_some();
+        match interpolation {
+            Interpolation::SpaceCoordinates => {
+                let (a, b) = (self.space_coordinates, other.space_coordinates);
+                Self {
+                    space_coordinates: (
+                        lerp(a.0 as f64, b.0 as f64) as f32,
+                        lerp(a.1 as f64, b.1 as f64) as f32,
+                    ),
+                    brightness: match (self.brightness, other.brightness) {
+                        (Some(a), Some(b)) => Some(lerp(a as f64, b as f64).round() as u8),
+                        _ => None,
+                    },
+                }
+            }
+            Interpolation::Lab => {
+                let lab = lerp3(xyz_to_lab(self.to_xyz()), xyz_to_lab(other.to_xyz()));
+                Self::from_xyz(lab_to_xyz(lab), has_brightness)
+            }
+            Interpolation::Oklab => {
+                let lab = lerp3(xyz_to_oklab(self.to_xyz()), xyz_to_oklab(other.to_xyz()));
+                Self::from_xyz(oklab_to_xyz(lab), has_brightness)
+            }
+        }
+    }
+
+    /// Converts the color to CIE XYZ values.
+    ///
+    /// If the color has no brightness, the maximum luminance is used.
+    fn to_xyz(self) -> [f64; 3] {
+        let (x, y) = (
+            self.space_coordinates.0 as f64,
+            self.space_coordinates.1 as f64,
+        );
+        let luminance = self.brightness.map_or(1.0, |v| v as f64 / 255.0);
+        if y <= 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+        [luminance / y * x, luminance, luminance / y * (1.0 - x - y)]
+    }
+
+    /// Creates a new color from CIE XYZ values.
+    fn from_xyz(xyz: [f64; 3], with_brightness: bool) -> Self {
+        let sum = xyz[0] + xyz[1] + xyz[2];
+        let space_coordinates = if sum <= 0.0 {
+            (0.0, 0.0)
+        } else {
+            ((xyz[0] / sum) as f32, (xyz[1] / sum) as f32)
+        };
+        Self {
+            space_coordinates,
+            brightness: if with_brightness {
+                Some((xyz[1] * 255.0).round().clamp(0.0, 255.0) as u8)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Creates a new color from rgb values that is limited to the gamut of a light.
+    ///
+    /// This changes the color and brightness of a light.
+    ///
+    /// # Examples
+    ///
+    /// Generate a green color that a light with gamut A can show.
+    /// ```
+    /// use huelib::color::{Color, Gamut};
+    ///
+    /// let green = Color::from_rgb_in_gamut(0, 255, 0, &Gamut::A);
+    /// assert!(Gamut::A.contains(green.space_coordinates()));
+    /// ```
+    pub fn from_rgb_in_gamut(red: u8, green: u8, blue: u8, gamut: &Gamut) -> Self {
+        Self::from_rgb(red, green, blue).in_gamut(gamut)
+    }
+
+    /// Returns the color with space coordinates that are limited to a gamut.
+    ///
+    /// Coordinates outside of the gamut are moved to the closest point on the edge of the gamut.
+    /// The brightness is not changed.
+    pub fn in_gamut(self, gamut: &Gamut) -> Self {
+        Self {
+            space_coordinates: gamut.project(self.space_coordinates),
+            ..self
+        }
+    }
+
+    /// Returns the color space coordinates of the color.
+    pub fn space_coordinates(&self) -> (f32, f32) {
+        self.space_coordinates
+    }
+
+    /// Returns the brightness of the color.
+    ///
+    /// Colors that only change the color of a light have no brightness.
+    pub fn brightness(&self) -> Option<u8> {
+        self.brightness
+    }
+
     /// Creates a new color from a hex value.
     ///
     /// This changes the color and brightness of a light.
     ///
-    /// The string must begin with a `#` followed by 3 or 6 hex values.
+    /// The string must begin with a `#` followed by 3, 4, 6 or 8 hex values. If an alpha value
+    /// is given, it is scaled to a brightness between 1 and 254.
     ///
     /// # Examples
     ///
@@ -69,27 +325,568 @@ impl Color {
     /// let color = Color::from_hex("#F40").unwrap();
     /// assert_eq!(color, Color::from_hex("#ff4400").unwrap())
     /// ```
+    ///
+    /// An opaque alpha value is the maximum brightness that is accepted by a light.
+    /// ```
+    /// # use huelib::Color;
+    /// let color = Color::from_hex("#FF0000FF").unwrap();
+    /// assert_eq!(color.brightness(), Some(254));
+    /// ```
     pub fn from_hex(s: impl AsRef<str>) -> Result<Self, ParseHexError> {
         let s = s.as_ref();
-        match s.len() {
-            4 => {
-                let red = u8::from_str_radix(&s[1..2], 16)?;
-                let green = u8::from_str_radix(&s[2..3], 16)?;
-                let blue = u8::from_str_radix(&s[3..4], 16)?;
-                Ok(Self::from_rgb(
-                    red * 16 + red,
-                    green * 16 + green,
-                    blue * 16 + blue,
-                ))
-            }
-            7 => Ok(Self::from_rgb(
-                u8::from_str_radix(&s[1..3], 16)?,
-                u8::from_str_radix(&s[3..5], 16)?,
-                u8::from_str_radix(&s[5..7], 16)?,
-            )),
-            _ => Err(ParseHexError::InvalidLenght),
+        if !s.is_ascii() {
+            return Err(ParseHexError::InvalidLenght);
         }
+        let short = |i: usize| -> Result<u8, ParseHexError> {
+            let v = u8::from_str_radix(&s[i..i + 1], 16)?;
+            Ok(v * 16 + v)
+        };
+        let long = |i: usize| u8::from_str_radix(&s[i..i + 2], 16);
+        let (red, green, blue, alpha) = match s.len() {
+            4 => (short(1)?, short(2)?, short(3)?, None),
+            5 => (short(1)?, short(2)?, short(3)?, Some(short(4)?)),
+            7 => (long(1)?, long(3)?, long(5)?, None),
+            9 => (long(1)?, long(3)?, long(5)?, Some(long(7)?)),
+            _ => return Err(ParseHexError::InvalidLenght),
+        };
+        let color = Self::from_rgb(red, green, blue);
+        Ok(match alpha {
+            Some(v) => Self {
+                brightness: Some(alpha_to_brightness(v as f32 / 255.0)),
+                ..color
+            },
+            None => color,
+        })
+    }
+
+    /// Creates a new color from hue, saturation and value.
+    ///
+    /// The hue is in degrees between 0 and 360, the saturation and value are between 0 and 1.
+    /// This changes the color and brightness of a light.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let (red, green, blue) = hsv_to_rgb(hue, saturation.clamp(0.0, 1.0), value.clamp(0.0, 1.0));
+        Self::from_rgb(red, green, blue)
     }
+
+    /// Creates a new color from hue, saturation and lightness.
+    ///
+    /// The hue is in degrees between 0 and 360, the saturation and lightness are between 0 and
+    /// 1. This changes the color and brightness of a light.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let value = lightness + saturation * lightness.min(1.0 - lightness);
+        let saturation = if value == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - lightness / value)
+        };
+        Self::from_hsv(hue, saturation, value)
+    }
+
+    /// Creates a new color from a named color of CSS.
+    ///
+    /// The name is case insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Color;
+    /// let color = Color::from_name("RebeccaPurple").unwrap();
+    /// assert_eq!(color, Color::from_hex("#663399").unwrap());
+    /// ```
+    pub fn from_name(name: impl AsRef<str>) -> Option<Self> {
+        let name = name.as_ref().to_ascii_lowercase();
+        let index = CSS_COLORS
+            .binary_search_by(|(v, _)| (*v).cmp(name.as_str()))
+            .ok()?;
+        let [_, red, green, blue] = CSS_COLORS[index].1.to_be_bytes();
+        Some(Self::from_rgb(red, green, blue))
+    }
+}
+
+/// Color space that is used to interpolate between colors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interpolation {
+    /// Interpolates the x and y coordinates in the CIE color space, like the bridge does when
+    /// it fades between colors.
+    SpaceCoordinates,
+    /// Interpolates in the CIELAB color space.
+    Lab,
+    /// Interpolates in the Oklab color space, which results in perceptually even steps.
+    Oklab,
+}
+
+/// Samples colors that are evenly distributed over a gradient.
+///
+/// The gradient runs through all stops in order. Returns an empty list if no stops are given.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::color::{self, Color, Interpolation};
+///
+/// let stops = [Color::from_rgb(255, 0, 0), Color::from_rgb(0, 0, 255)];
+/// let colors = color::gradient(&stops, 5, Interpolation::Oklab);
+/// assert_eq!(colors.len(), 5);
+/// assert_eq!(colors[0], stops[0]);
+/// assert_eq!(colors[4], stops[1]);
+/// ```
+pub fn gradient(stops: &[Color], count: usize, interpolation: Interpolation) -> Vec<Color> {
+    match (stops, count) {
+        ([], _) | (_, 0) => Vec::new(),
+        ([stop], _) => vec![*stop; count],
+        (_, 1) => vec![stops[0]],
+        _ => (0..count)
+            .map(|i| {
+                let position = i as f32 / (count - 1) as f32 * (stops.len() - 1) as f32;
+                let index = (position.floor() as usize).min(stops.len() - 2);
+                stops[index].interpolate(&stops[index + 1], position - index as f32, interpolation)
+            })
+            .collect(),
+    }
+}
+
+/// Spreads a gradient over lights and returns a state modifier for each light.
+///
+/// # Examples
+///
+/// Spread a gradient over the lights of a group.
+/// ```no_run
+/// use huelib::color::{self, Color, Interpolation};
+/// use huelib::Bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+/// let group = bridge.get_group("1").unwrap();
+/// let stops = [Color::from_name("orange").unwrap(), Color::from_name("purple").unwrap()];
+/// for (id, modifier) in color::gradient_modifiers(&group.lights, &stops, Interpolation::Oklab) {
+///     bridge.set_light_state(id, &modifier).unwrap();
+/// }
+/// ```
+pub fn gradient_modifiers(
+    lights: &[impl AsRef<str>],
+    stops: &[Color],
+    interpolation: Interpolation,
+) -> Vec<(LightId, light::StateModifier)> {
+    lights
+        .iter()
+        .zip(gradient(stops, lights.len(), interpolation))
+        .map(|(id, color)| {
+            (
+                LightId::from(id.as_ref()),
+                light::StateModifier::new().color(color),
+            )
+        })
+        .collect()
+}
+
+/// Converts a color temperature in kelvin to mired.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::color::kelvin_to_mired;
+///
+/// assert_eq!(kelvin_to_mired(6500), 154);
+/// ```
+pub fn kelvin_to_mired(kelvin: u16) -> u16 {
+    (1_000_000.0 / kelvin.max(1) as f32)
+        .round()
+        .min(u16::MAX as f32) as u16
+}
+
+/// Converts a color temperature in mired to kelvin.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::color::mired_to_kelvin;
+///
+/// assert_eq!(mired_to_kelvin(500), 2000);
+/// ```
+pub fn mired_to_kelvin(mired: u16) -> u16 {
+    (1_000_000.0 / mired.max(1) as f32)
+        .round()
+        .min(u16::MAX as f32) as u16
+}
+
+/// Triangle in the color space that contains the colors a light can show.
+///
+/// The gamut of a light is returned by [`ControlCapabilities::gamut`].
+///
+/// [`ControlCapabilities::gamut`]: ../resource/light/struct.ControlCapabilities.html#method.gamut
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gamut {
+    /// Color space coordinates of the red corner.
+    pub red: (f32, f32),
+    /// Color space coordinates of the green corner.
+    pub green: (f32, f32),
+    /// Color space coordinates of the blue corner.
+    pub blue: (f32, f32),
+}
+
+impl Gamut {
+    /// Gamut of older color lights, like LivingColors and LightStrips.
+    pub const A: Self = Self {
+        red: (0.704, 0.296),
+        green: (0.2151, 0.7106),
+        blue: (0.138, 0.08),
+    };
+
+    /// Gamut of the first generation of Hue bulbs.
+    pub const B: Self = Self {
+        red: (0.675, 0.322),
+        green: (0.409, 0.518),
+        blue: (0.167, 0.04),
+    };
+
+    /// Gamut of newer Hue bulbs and LightStrips.
+    pub const C: Self = Self {
+        red: (0.6915, 0.3083),
+        green: (0.17, 0.7),
+        blue: (0.1532, 0.0475),
+    };
+
+    /// Creates a gamut from the three corners returned by the bridge.
+    ///
+    /// Returns `None` if not exactly three corners are given.
+    pub fn from_points(points: &[(f32, f32)]) -> Option<Self> {
+        match points {
+            [red, green, blue] => Some(Self {
+                red: *red,
+                green: *green,
+                blue: *blue,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the built-in gamut for a gamut type (`A`, `B` or `C`).
+    pub fn from_type(value: &str) -> Option<Self> {
+        match value {
+            "A" => Some(Self::A),
+            "B" => Some(Self::B),
+            "C" => Some(Self::C),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the color space coordinates are inside of the gamut.
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        let side = |a: (f32, f32), b: (f32, f32)| cross(sub(b, a), sub(point, a));
+        let sides = [
+            side(self.red, self.green),
+            side(self.green, self.blue),
+            side(self.blue, self.red),
+        ];
+        sides.iter().all(|v| *v >= -f32::EPSILON) || sides.iter().all(|v| *v <= f32::EPSILON)
+    }
+
+    /// Returns the closest color space coordinates that are inside of the gamut.
+    ///
+    /// # Examples
+    ///
+    /// Project a saturated red onto the edge of gamut B.
+    /// ```
+    /// use huelib::color::Gamut;
+    ///
+    /// let point = Gamut::B.project((0.8, 0.3));
+    /// assert!(Gamut::B.contains(point));
+    /// assert_eq!(Gamut::B.project(point), point);
+    /// ```
+    pub fn project(&self, point: (f32, f32)) -> (f32, f32) {
+        if self.contains(point) {
+            return point;
+        }
+        let edges = [
+            closest_point_on_line(self.red, self.green, point),
+            closest_point_on_line(self.green, self.blue, point),
+            closest_point_on_line(self.blue, self.red, point),
+        ];
+        let distance = |v: (f32, f32)| {
+            let d = sub(v, point);
+            d.0 * d.0 + d.1 * d.1
+        };
+        edges
+            .iter()
+            .copied()
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap_or(point)
+    }
+}
+
+/// Converts hue in degrees, saturation and value to rgb values.
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (u8, u8, u8) {
+    let chroma = value * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (red, green, blue) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let to_u8 = |v: f32| ((v + m) * 255.0).round() as u8;
+    (to_u8(red), to_u8(green), to_u8(blue))
+}
+
+/// Approximates the color space coordinates of a color temperature in kelvin.
+fn planckian_locus(kelvin: f32) -> (f32, f32) {
+    // NOTE: More information: https://en.wikipedia.org/wiki/Planckian_locus#Approximation
+    let t = kelvin.clamp(1667.0, 25000.0) as f64;
+    let x = if t <= 4000.0 {
+        -0.266_123_9e9 / t.powi(3) - 0.234_358_9e6 / t.powi(2) + 0.877_695_6e3 / t + 0.179_910
+    } else {
+        -3.025_846_9e9 / t.powi(3) + 2.107_037_9e6 / t.powi(2) + 0.222_634_7e3 / t + 0.240_390
+    };
+    let y = if t <= 2222.0 {
+        -1.106_381_4 * x.powi(3) - 1.348_110_20 * x.powi(2) + 2.185_558_32 * x - 0.202_196_83
+    } else if t <= 4000.0 {
+        -0.954_947_6 * x.powi(3) - 1.374_185_93 * x.powi(2) + 2.091_370_15 * x - 0.167_488_67
+    } else {
+        3.081_758_0 * x.powi(3) - 5.873_386_70 * x.powi(2) + 3.751_129_97 * x - 0.370_014_83
+    };
+    (x as f32, y as f32)
+}
+
+/// White point of the D65 illuminant.
+const D65: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+
+fn xyz_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |v: f64| {
+        if v > 216.0 / 24389.0 {
+            v.cbrt()
+        } else {
+            (24389.0 / 27.0 * v + 16.0) / 116.0
+        }
+    };
+    let (x, y, z) = (f(xyz[0] / D65[0]), f(xyz[1] / D65[1]), f(xyz[2] / D65[2]));
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+fn lab_to_xyz(lab: [f64; 3]) -> [f64; 3] {
+    let y = (lab[0] + 16.0) / 116.0;
+    let x = y + lab[1] / 500.0;
+    let z = y - lab[2] / 200.0;
+    let f = |v: f64| {
+        if v.powi(3) > 216.0 / 24389.0 {
+            v.powi(3)
+        } else {
+            (116.0 * v - 16.0) * 27.0 / 24389.0
+        }
+    };
+    [f(x) * D65[0], f(y) * D65[1], f(z) * D65[2]]
+}
+
+fn xyz_to_oklab(xyz: [f64; 3]) -> [f64; 3] {
+    // NOTE: More information: https://bottosson.github.io/posts/oklab/
+    let [x, y, z] = xyz;
+    let l = (0.818_933_010_1 * x + 0.361_866_742_4 * y - 0.128_859_713_7 * z).cbrt();
+    let m = (0.032_984_543_6 * x + 0.929_311_871_5 * y + 0.036_145_638_7 * z).cbrt();
+    let s = (0.048_200_301_8 * x + 0.264_366_269_1 * y + 0.633_851_707 * z).cbrt();
+    [
+        0.210_454_255_3 * l + 0.793_617_785 * m - 0.004_072_046_8 * s,
+        1.977_998_495_1 * l - 2.428_592_205 * m + 0.450_593_709_9 * s,
+        0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766 * s,
+    ]
+}
+
+fn oklab_to_xyz(lab: [f64; 3]) -> [f64; 3] {
+    let [lightness, a, b] = lab;
+    let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+    let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+    let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548 * b).powi(3);
+    [
+        1.227_013_851_1 * l - 0.557_799_980_7 * m + 0.281_256_149 * s,
+        -0.040_580_178_4 * l + 1.112_256_869_6 * m - 0.071_676_678_7 * s,
+        -0.076_381_284_5 * l - 0.421_481_978_4 * m + 1.586_163_220_4 * s,
+    ]
+}
+
+fn sub(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn cross(a: (f32, f32), b: (f32, f32)) -> f32 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn closest_point_on_line(a: (f32, f32), b: (f32, f32), point: (f32, f32)) -> (f32, f32) {
+    let ab = sub(b, a);
+    let ap = sub(point, a);
+    let t = ((ap.0 * ab.0 + ap.1 * ab.1) / (ab.0 * ab.0 + ab.1 * ab.1)).clamp(0.0, 1.0);
+    (a.0 + ab.0 * t, a.1 + ab.1 * t)
+}
+
+/// Serializes the color as color space coordinates and brightness.
+///
+/// # Examples
+///
+/// ```
+/// # use huelib::Color;
+/// let color = Color::from_space_coordinates(0.5, 0.25);
+/// let json = serde_json::to_string(&color).unwrap();
+/// assert_eq!(json, r#"{"x":0.5,"y":0.25}"#);
+/// assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+///
+/// let color = Color::from_rgb(255, 128, 0);
+/// let json = serde_json::to_string(&color).unwrap();
+/// assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+/// ```
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ColorRepr::Coordinates {
+            x: self.space_coordinates.0,
+            y: self.space_coordinates.1,
+            bri: self.brightness,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes the color from color space coordinates and brightness, or from any string that
+/// can be [parsed](#impl-FromStr) into a color.
+///
+/// # Examples
+///
+/// ```
+/// # use huelib::Color;
+/// let color: Color = serde_json::from_str(r##""#FF0000""##).unwrap();
+/// assert_eq!(color, Color::from_rgb(255, 0, 0));
+///
+/// let color: Color = serde_json::from_str(r#"{"x": 0.3, "y": 0.3, "bri": 100}"#).unwrap();
+/// assert_eq!(color.space_coordinates(), (0.3, 0.3));
+/// assert_eq!(color.brightness(), Some(100));
+/// ```
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match ColorRepr::deserialize(deserializer)? {
+            ColorRepr::String(v) => v.parse().map_err(de::Error::custom),
+            ColorRepr::Coordinates { x, y, bri } => Ok(Self {
+                space_coordinates: (x, y),
+                brightness: bri,
+            }),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum ColorRepr {
+    String(String),
+    Coordinates {
+        x: f32,
+        y: f32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bri: Option<u8>,
+    },
+}
+
+impl FromStr for Color {
+    type Err = ParseError;
+
+    /// Parses a color from a string.
+    ///
+    /// Accepted are hex values (`#RGB`, `#RGBA`, `#RRGGBB` and `#RRGGBBAA`), the functional
+    /// notations `rgb()`, `rgba()`, `hsl()` and `hsla()`, and named colors of CSS.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use huelib::Color;
+    /// let red: Color = "red".parse().unwrap();
+    /// assert_eq!(red, "#FF0000".parse().unwrap());
+    /// assert_eq!(red, "rgb(255, 0, 0)".parse().unwrap());
+    /// assert_eq!(red, "hsl(0, 100%, 50%)".parse().unwrap());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('#') {
+            return Ok(Self::from_hex(s)?);
+        }
+        if let Some(v) = s.find('(') {
+            let function = s[..v].trim().to_ascii_lowercase();
+            let arguments = s[v + 1..]
+                .strip_suffix(')')
+                .ok_or_else(|| ParseError::InvalidFormat(s.to_owned()))?;
+            let arguments: Vec<&str> = arguments
+                .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+                .filter(|v| !v.is_empty())
+                .collect();
+            let (values, alpha) = match (function.as_str(), arguments.len()) {
+                ("rgb", 3) | ("hsl", 3) => (&arguments[..], None),
+                ("rgb", 4) | ("rgba", 4) | ("hsl", 4) | ("hsla", 4) => {
+                    (&arguments[..3], Some(parse_alpha(arguments[3])?))
+                }
+                _ => return Err(ParseError::InvalidFormat(s.to_owned())),
+            };
+            let color = if function.starts_with("rgb") {
+                let channel = |v: &str| parse_number(v, 255.0).map(|v| v.round() as u8);
+                Self::from_rgb(
+                    channel(values[0])?,
+                    channel(values[1])?,
+                    channel(values[2])?,
+                )
+            } else {
+                let hue = values[0].trim_end_matches("deg");
+                let hue: f32 = hue
+                    .parse()
+                    .map_err(|_| ParseError::InvalidValue(values[0].to_owned()))?;
+                Self::from_hsl(
+                    hue,
+                    parse_number(values[1], 1.0)?,
+                    parse_number(values[2], 1.0)?,
+                )
+            };
+            return Ok(match alpha {
+                Some(v) => Self {
+                    brightness: Some(v),
+                    ..color
+                },
+                None => color,
+            });
+        }
+        Self::from_name(s).ok_or_else(|| ParseError::InvalidFormat(s.to_owned()))
+    }
+}
+
+/// Parses a number or a percentage of the maximum value.
+fn parse_number(s: &str, max: f32) -> Result<f32, ParseError> {
+    let value = match s.strip_suffix('%') {
+        Some(v) => v.parse::<f32>().map(|v| v / 100.0 * max),
+        None => s.parse::<f32>(),
+    };
+    value
+        .map(|v| v.clamp(0.0, max))
+        .map_err(|_| ParseError::InvalidValue(s.to_owned()))
+}
+
+/// Parses an alpha value between 0 and 1 or a percentage to a brightness.
+fn parse_alpha(s: &str) -> Result<u8, ParseError> {
+    parse_number(s, 1.0).map(alpha_to_brightness)
+}
+
+/// Scales an alpha value between 0 and 1 to a brightness between 1 and 254.
+fn alpha_to_brightness(alpha: f32) -> u8 {
+    (alpha.clamp(0.0, 1.0) * 254.0).round().max(1.0) as u8
+}
+
+/// Errors that can occur while parsing a string to a color.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ParseError {
+    /// Error that occurs when the format of the string is not supported.
+    #[error("Invalid color format: {0}")]
+    InvalidFormat(String),
+    /// Error that occurs when a value of a functional notation is invalid.
+    #[error("Invalid color value: {0}")]
+    InvalidValue(String),
+    /// Error that can occur while parsing a hex string.
+    #[error("Failed to parse a hex value")]
+    Hex(#[from] ParseHexError),
 }
 
 /// Errors that can occur while parsing a hex string to a color.
@@ -102,3 +899,155 @@ pub enum ParseHexError {
     #[error("Failed to parse a int value")]
     ParseInt(#[from] ParseIntError),
 }
+
+/// Named colors of CSS, sorted by name.
+const CSS_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
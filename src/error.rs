@@ -1,4 +1,8 @@
-use crate::response::Error as ResponseError;
+use crate::backup::ArchiveError;
+use crate::bridge::RequestContext;
+use crate::reconcile::ReconcileError;
+use crate::resolve::ReferenceError;
+use crate::response::{Error as ResponseError, ErrorCategory};
 use chrono::ParseError as ChronoParseError;
 use serde_json::Error as SerdeJsonError;
 use std::result::Result as StdResult;
@@ -17,19 +21,173 @@ pub enum Error {
     /// Error that can occur when the identifier of a newly created resource cannot be obtained.
     #[error("Failed to get identifier of created resource")]
     GetCreatedId,
+    /// Error that can occur when a request cannot be sent or the response cannot be received.
+    #[error("Failed to send request: {0}")]
+    Transport(String),
+    /// Error that can occur when the bridge does not respond in time.
+    #[error("Request timed out")]
+    Timeout,
+    /// Error that can occur when the bridge responds with a status other than 2xx or with a
+    /// body that is not valid JSON.
+    #[error("Unexpected response with status {status}: {body}")]
+    UnexpectedResponse {
+        /// HTTP status code of the response.
+        status: u16,
+        /// Beginning of the response body.
+        body: String,
+    },
     /// Error that can occur while converting a string to a date.
     #[error("Failed to parse date: {0}")]
     ParseDate(#[from] ChronoParseError),
     /// Error that can occur while converting a http response into a string.
     #[error("Failed to parse http response: {0}")]
     ParseHttpResponse(#[from] IoError),
+    /// Error that can occur while loading an image.
+    #[cfg(feature = "image")]
+    #[error("Failed to load image: {0}")]
+    Image(#[from] image::ImageError),
     /// Error that can occur while converting a string to an IP address.
     #[error("Failed to parse ip address: {0}")]
     ParseIpAddr(#[from] AddrParseError),
     /// Error that can occur while parsing json content.
     #[error("Failed to parse json content: {0}")]
     ParseJson(#[from] SerdeJsonError),
+    /// Error that can occur while resolving resources by name.
+    #[error("Failed to resolve reference: {0}")]
+    Reference(#[from] ReferenceError),
+    /// Error that can occur while planning the reconciliation of a bridge.
+    #[error("Failed to reconcile bridge: {0}")]
+    Reconcile(#[from] ReconcileError),
+    /// Error that can occur while importing an archive.
+    #[error("Failed to import archive: {0}")]
+    Archive(#[from] ArchiveError),
     /// Error that is returned by the Philips Hue API.
     #[error("Error returned from Philips Hue API: {0}")]
     Response(#[from] ResponseError),
+    /// Error that occurred while sending a request to the Philips Hue API.
+    #[error("{context} failed: {source}")]
+    Request {
+        /// Context of the request.
+        context: RequestContext,
+        /// Error that occurred.
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the context of the request that failed.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Request { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without the context of the request.
+    pub fn inner(&self) -> &Self {
+        match self {
+            Self::Request { source, .. } => source.inner(),
+            v => v,
+        }
+    }
+
+    /// Returns whether the bridge did not respond in time.
+    ///
+    /// Errors of requests are wrapped in [`Error::Request`], so this should be used instead of
+    /// matching [`Error::Timeout`].
+    ///
+    /// [`Error::Request`]: #variant.Request
+    /// [`Error::Timeout`]: #variant.Timeout
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use std::time::Duration;
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username")
+    ///     .connect_timeout(Duration::from_secs(2));
+    /// match bridge.get_config() {
+    ///     Ok(v) => println!("{:?}", v),
+    ///     Err(e) if e.is_timeout() => eprintln!("Bridge did not respond in time"),
+    ///     Err(e) => eprintln!("{}", e),
+    /// }
+    /// ```
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.inner(), Self::Timeout)
+    }
+
+    /// Returns whether the request could not be sent or the response could not be received.
+    ///
+    /// This includes certificates that are rejected, see [`Bridge::https`].
+    ///
+    /// [`Bridge::https`]: bridge/struct.Bridge.html#method.https
+    pub fn is_transport(&self) -> bool {
+        matches!(self.inner(), Self::Transport(_))
+    }
+
+    /// Returns the error that was returned by the Philips Hue API.
+    pub fn response_error(&self) -> Option<&ResponseError> {
+        match self.inner() {
+            Self::Response(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns the category of an error that was returned by the Philips Hue API.
+    ///
+    /// Responses with the HTTP status 401, 403 or 404 are categorized as well.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib::response::ErrorCategory;
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// match bridge.get_light("1") {
+    ///     Ok(v) => println!("{:?}", v),
+    ///     Err(e) if e.is_not_found() => eprintln!("Light does not exist"),
+    ///     Err(e) if e.category() == Some(ErrorCategory::Unauthorized) => {
+    ///         eprintln!("User is not registered")
+    ///     }
+    ///     Err(e) => eprintln!("{}", e),
+    /// }
+    /// ```
+    pub fn category(&self) -> Option<ErrorCategory> {
+        match self.inner() {
+            Self::Response(e) => Some(e.kind.category()),
+            Self::UnexpectedResponse { status: 401, .. }
+            | Self::UnexpectedResponse { status: 403, .. } => Some(ErrorCategory::Unauthorized),
+            Self::UnexpectedResponse { status: 404, .. } => Some(ErrorCategory::NotFound),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the user is not authorized or the link button was not pressed.
+    pub fn is_unauthorized(&self) -> bool {
+        self.category() == Some(ErrorCategory::Unauthorized)
+    }
+
+    /// Returns whether the resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        self.category() == Some(ErrorCategory::NotFound)
+    }
+
+    /// Returns whether a list or table of the bridge is full.
+    pub fn is_full(&self) -> bool {
+        self.category() == Some(ErrorCategory::Full)
+    }
+
+    /// Returns whether a parameter of the request is missing or invalid.
+    pub fn is_invalid_parameter(&self) -> bool {
+        self.category() == Some(ErrorCategory::InvalidParameter)
+    }
+
+    /// Returns whether the device is turned off.
+    pub fn is_device_off(&self) -> bool {
+        self.category() == Some(ErrorCategory::DeviceOff)
+    }
 }
@@ -11,6 +11,12 @@
 //! [serde_json]: https://github.com/serde-rs/json
 //! [serde_repr]: https://github.com/dtolnay/serde-repr
 //!
+//! ## Features
+//!
+//! - `image`: Generates color palettes from images, see the [`palette`] module.
+//!
+//! [`palette`]: palette/index.html
+//!
 //! ## Examples
 //!
 //! Modifies the state of a light on a specific bridge:
@@ -28,9 +34,9 @@
 //!     .brightness(ModifierType::Increment, 40)
 //!     .saturation(ModifierType::Override, 200);
 //!
-//! // Set attributes of the light with index '1' from the modifier and print the responses.
+//! // Set attributes of the light with index '1' from the modifier and print the result.
 //! match bridge.set_light_state("1", &modifier) {
-//!     Ok(v) => v.iter().for_each(|response| println!("{}", response)),
+//!     Ok(v) => println!("{}", v),
 //!     Err(e) => eprintln!("Failed to modify the light state: {}", e),
 //! };
 //! ```
@@ -66,19 +72,55 @@
 
 #![deny(missing_docs, missing_debug_implementations, unreachable_pub, unsafe_code)]
 
+/// Module for exporting and importing the configuration of bridges.
+pub mod backup;
 /// Module for managing bridges.
 pub mod bridge;
+/// Module for brightness values.
+pub mod brightness;
 /// Module for generating colors.
 pub mod color;
+/// Module for generating colors from images.
+///
+/// This module is only available with the `image` feature.
+///
+/// # Examples
+///
+/// Create a scene for a group from a photo.
+/// ```no_run
+/// use huelib::{palette, Bridge};
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+/// let group = bridge.get_group("1").unwrap();
+/// let lights = bridge.get_all_lights().unwrap();
+/// let image = palette::open("sunset.jpg").unwrap();
+/// let method = palette::Method::KMeans;
+/// let creator = palette::scene_creator("Sunset", &group, &lights, &image, method);
+/// bridge.create_scene(&creator).unwrap();
+/// ```
+#[cfg(feature = "image")]
+pub mod palette;
+/// Module for reconciling bridges with a declarative configuration.
+pub mod reconcile;
+/// Module for resolving resources by name and selecting lights.
+pub mod resolve;
 /// Module for bridge resources.
 pub mod resource;
 /// Responses returned from the Philips Hue API.
 pub mod response;
+/// Module for limiting the rate of requests to bridges.
+pub mod scheduler;
+/// Module for serializing resources with the field names of this library.
+pub mod snake_case;
+/// Module for validating certificates of bridges.
+pub mod tls;
 
 mod error;
 mod util;
 
 pub use bridge::Bridge;
+pub use brightness::Brightness;
 pub use color::Color;
 pub use error::{Error, Result};
 pub use response::Response;
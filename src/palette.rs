@@ -0,0 +1,187 @@
+use crate::resource::{group::Group, light::Light, scene, Modifier};
+use crate::Result;
+use image::DynamicImage;
+use std::cmp::Reverse;
+use std::path::Path;
+
+/// Maximal width and height of the image that the colors are extracted from.
+const SAMPLE_SIZE: u32 = 64;
+
+/// Maximal number of k-means iterations.
+const MAX_ITERATIONS: usize = 20;
+
+/// Algorithm that is used to pick the dominant colors of an image.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// Splits the colors of the image into boxes along their widest channel.
+    MedianCut,
+    /// Refines the result of the median cut with k-means clustering.
+    KMeans,
+}
+
+/// Opens a PNG or JPEG image.
+pub fn open(path: impl AsRef<Path>) -> Result<DynamicImage> {
+    Ok(image::open(path)?)
+}
+
+/// Returns up to `count` dominant rgb colors of an image, ordered by how often they occur.
+///
+/// Transparent pixels are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::palette::{self, Method};
+/// use image::{DynamicImage, Rgb, RgbImage};
+///
+/// let image = RgbImage::from_fn(8, 8, |x, _| {
+///     if x < 6 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) }
+/// });
+/// let colors = palette::extract(&DynamicImage::ImageRgb8(image), 2, Method::KMeans);
+/// assert_eq!(colors, vec![(255, 0, 0), (0, 0, 255)]);
+/// ```
+pub fn extract(image: &DynamicImage, count: usize, method: Method) -> Vec<(u8, u8, u8)> {
+    let sample = if image.width() > SAMPLE_SIZE || image.height() > SAMPLE_SIZE {
+        image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE)
+    } else {
+        image.clone()
+    };
+    let pixels: Vec<[f32; 3]> = sample
+        .to_rgba8()
+        .pixels()
+        .filter(|v| v[3] >= 128)
+        .map(|v| [v[0] as f32, v[1] as f32, v[2] as f32])
+        .collect();
+    if pixels.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    let mut clusters = median_cut(&pixels, count);
+    if method == Method::KMeans {
+        clusters = k_means(&pixels, clusters);
+    }
+    clusters.sort_by_key(|v| Reverse(v.1));
+    clusters
+        .into_iter()
+        .map(|(v, _)| {
+            let to_u8 = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+            (to_u8(v[0]), to_u8(v[1]), to_u8(v[2]))
+        })
+        .collect()
+}
+
+/// Creates a scene for a group with the dominant colors of an image.
+///
+/// The lights of the group are looked up in `lights`. Every light gets one of the dominant
+/// colors, limited to the gamut of the light. Lights that do not support colors are only turned
+/// on with the brightness of the color.
+pub fn scene_creator(
+    name: impl Into<String>,
+    group: &Group,
+    lights: &[Light],
+    image: &DynamicImage,
+    method: Method,
+) -> scene::Creator {
+    let lights: Vec<&Light> = group
+        .lights
+        .iter()
+        .filter_map(|id| lights.iter().find(|v| &v.id == id))
+        .collect();
+    let colors = extract(image, lights.len(), method);
+    let mut creator = scene::Creator::new(name, lights.iter().map(|v| v.id.clone()).collect())
+        .kind(scene::Kind::GroupScene)
+        .group(&group.id);
+    for (light, (red, green, blue)) in lights.iter().zip(colors.iter().cycle()) {
+        let brightness = ((*red).max(*green).max(*blue) as u16 * 254 / 255).max(1) as u8;
+        let mut state = scene::LightStateModifier::new().on(true);
+        if let Some(gamut) = light.capabilities.control.gamut() {
+            state = state.color(crate::Color::from_rgb_in_gamut(*red, *green, *blue, &gamut));
+        }
+        creator = creator.light_state(&light.id, state.brightness(brightness));
+    }
+    creator
+}
+
+/// Splits pixels into up to `count` clusters and returns their mean and size.
+fn median_cut(pixels: &[[f32; 3]], count: usize) -> Vec<([f32; 3], usize)> {
+    let mut boxes = vec![pixels.to_vec()];
+    while boxes.len() < count {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.len() > 1)
+            .map(|(i, v)| {
+                let (channel, range) = widest_channel(v);
+                (i, channel, range)
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2));
+        let (index, channel) = match widest {
+            Some((i, channel, range)) if range > 0.0 => (i, channel),
+            _ => break,
+        };
+        let mut pixels = boxes.swap_remove(index);
+        pixels.sort_by(|a, b| a[channel].total_cmp(&b[channel]));
+        let upper = pixels.split_off(pixels.len() / 2);
+        boxes.push(pixels);
+        boxes.push(upper);
+    }
+    boxes.iter().map(|v| (mean(v), v.len())).collect()
+}
+
+/// Refines clusters by assigning every pixel to the closest mean until the means are stable.
+fn k_means(pixels: &[[f32; 3]], clusters: Vec<([f32; 3], usize)>) -> Vec<([f32; 3], usize)> {
+    let mut means: Vec<[f32; 3]> = clusters.into_iter().map(|(v, _)| v).collect();
+    let mut members = vec![Vec::new(); means.len()];
+    for _ in 0..MAX_ITERATIONS {
+        members.iter_mut().for_each(Vec::clear);
+        for pixel in pixels {
+            let closest = means
+                .iter()
+                .enumerate()
+                .min_by(|a, b| distance(a.1, pixel).total_cmp(&distance(b.1, pixel)))
+                .map_or(0, |(i, _)| i);
+            members[closest].push(*pixel);
+        }
+        let updated: Vec<[f32; 3]> = members
+            .iter()
+            .zip(&means)
+            .map(|(v, old)| if v.is_empty() { *old } else { mean(v) })
+            .collect();
+        let converged = updated
+            .iter()
+            .zip(&means)
+            .all(|(a, b)| distance(a, b) < 1.0);
+        means = updated;
+        if converged {
+            break;
+        }
+    }
+    means
+        .into_iter()
+        .zip(members.iter().map(Vec::len))
+        .filter(|(_, size)| *size > 0)
+        .collect()
+}
+
+fn widest_channel(pixels: &[[f32; 3]]) -> (usize, f32) {
+    (0..3)
+        .map(|channel| {
+            let (min, max) = pixels.iter().fold((f32::MAX, f32::MIN), |(min, max), v| {
+                (min.min(v[channel]), max.max(v[channel]))
+            });
+            (channel, max - min)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+fn mean(pixels: &[[f32; 3]]) -> [f32; 3] {
+    let sum = pixels.iter().fold([0.0; 3], |sum, v| {
+        [sum[0] + v[0], sum[1] + v[1], sum[2] + v[2]]
+    });
+    let len = pixels.len().max(1) as f32;
+    [sum[0] / len, sum[1] / len, sum[2] / len]
+}
+
+fn distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
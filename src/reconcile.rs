@@ -0,0 +1,775 @@
+use crate::resource::{
+    self, group, resourcelink::LinkKind, scene, schedule, Action, ActionRequestType, Light,
+    Modifier,
+};
+use crate::{Bridge, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use thiserror::Error as ThisError;
+
+/// Desired state of the rooms, zones, scenes and schedules of a bridge.
+///
+/// The document can be deserialized from any format that is supported by serde, for example
+/// YAML or TOML. Lights are referenced by their name or identifier, groups and scenes by their
+/// name without the prefix of the reconciler.
+///
+/// # Examples
+///
+/// Parse a document from JSON.
+/// ```
+/// use huelib::reconcile::Document;
+///
+/// let document: Document = serde_json::from_str(r#"{
+///     "rooms": [{ "name": "Kitchen", "class": "Kitchen", "lights": ["Kitchen Spot"] }],
+///     "scenes": [{
+///         "name": "Bright",
+///         "group": "Kitchen",
+///         "lights": { "Kitchen Spot": { "on": true, "bri": 254 } }
+///     }],
+///     "schedules": [{
+///         "name": "Morning",
+///         "local_time": "W124/T07:00:00",
+///         "group": "Kitchen",
+///         "body": { "scene": "Bright" }
+///     }]
+/// }"#).unwrap();
+/// assert_eq!(document.rooms[0].lights, vec!["Kitchen Spot"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Document {
+    /// Rooms of the bridge.
+    #[serde(default)]
+    pub rooms: Vec<GroupSpec>,
+    /// Zones of the bridge.
+    #[serde(default)]
+    pub zones: Vec<GroupSpec>,
+    /// Scenes of the bridge.
+    #[serde(default)]
+    pub scenes: Vec<SceneSpec>,
+    /// Schedules of the bridge.
+    #[serde(default)]
+    pub schedules: Vec<ScheduleSpec>,
+}
+
+/// Desired state of a room or zone.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct GroupSpec {
+    /// Name of the group.
+    pub name: String,
+    /// Class of the group.
+    pub class: Option<group::Class>,
+    /// Names or identifiers of the lights in the group.
+    #[serde(default)]
+    pub lights: Vec<String>,
+}
+
+/// Desired state of a scene.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SceneSpec {
+    /// Name of the scene.
+    pub name: String,
+    /// Name of the group that the scene is linked to.
+    pub group: String,
+    /// States of the lights in the scene, keyed by the name or identifier of the light.
+    pub lights: BTreeMap<String, scene::LightStateModifier>,
+}
+
+/// Desired state of a schedule.
+///
+/// The schedule sends the body to the action of a group. A `scene` in the body is resolved by
+/// name.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ScheduleSpec {
+    /// Name of the schedule.
+    pub name: String,
+    /// Description of the schedule.
+    #[serde(default)]
+    pub description: String,
+    /// Time when the scheduled event will occur.
+    pub local_time: String,
+    /// Name of the group that the action is sent to.
+    pub group: String,
+    /// Body of the action.
+    pub body: HashMap<String, JsonValue>,
+    /// Whether the schedule is enabled.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Reference to a resource that exists or is created by an earlier operation of a plan.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Target {
+    /// Identifier of an existing resource.
+    Existing(String),
+    /// Name of a resource that is created by the plan.
+    Planned(String),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Existing(v) => write!(f, "{}", v),
+            Self::Planned(v) => write!(f, "'{}' (known after apply)", v),
+        }
+    }
+}
+
+/// Action of a schedule whose targets are resolved when the plan is applied.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlannedAction {
+    /// Group that the action is sent to.
+    pub group: Target,
+    /// Scene that is recalled by the action.
+    pub scene: Option<Target>,
+    /// Body of the action.
+    pub body: HashMap<String, JsonValue>,
+}
+
+impl PlannedAction {
+    fn resolve(&self, username: &str, created: &Created) -> Result<Action> {
+        let mut body = self.body.clone();
+        if let Some(v) = &self.scene {
+            body.insert(
+                "scene".to_owned(),
+                created.resolve(LinkKind::Scene, v)?.into(),
+            );
+        }
+        Ok(Action {
+            address: format!(
+                "/api/{}/groups/{}/action",
+                username,
+                created.resolve(LinkKind::Group, &self.group)?
+            ),
+            request_type: ActionRequestType::Put,
+            body,
+        })
+    }
+
+    /// Whether the action equals an existing action, ignoring the username in the address.
+    fn matches(&self, action: &Action) -> bool {
+        let created = Created::default();
+        match self.resolve("", &created) {
+            Ok(v) => {
+                strip_username(&v.address) == strip_username(&action.address)
+                    && v.request_type == action.request_type
+                    && v.body == action.body
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// An operation of a plan.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+    /// Creates a group.
+    CreateGroup {
+        name: String,
+        creator: group::Creator,
+    },
+    /// Modifies attributes of a group.
+    SetGroup {
+        id: resource::GroupId,
+        name: String,
+        modifier: group::AttributeModifier,
+    },
+    /// Deletes a group.
+    DeleteGroup { id: resource::GroupId, name: String },
+    /// Creates a scene that is linked to a group.
+    CreateScene {
+        name: String,
+        group: Target,
+        creator: scene::Creator,
+    },
+    /// Modifies a scene.
+    SetScene {
+        id: resource::SceneId,
+        name: String,
+        modifier: scene::Modifier,
+    },
+    /// Deletes a scene.
+    DeleteScene { id: resource::SceneId, name: String },
+    /// Creates a schedule.
+    CreateSchedule {
+        name: String,
+        description: String,
+        local_time: String,
+        status: schedule::Status,
+        action: PlannedAction,
+    },
+    /// Modifies attributes of a schedule.
+    SetSchedule {
+        id: resource::ScheduleId,
+        name: String,
+        action: Option<PlannedAction>,
+        modifier: schedule::Modifier,
+    },
+    /// Deletes a schedule.
+    DeleteSchedule {
+        id: resource::ScheduleId,
+        name: String,
+    },
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CreateGroup { name, .. } => write!(f, "+ create group '{}'", name),
+            Self::SetGroup { id, name, .. } => write!(f, "~ modify group '{}' ({})", name, id),
+            Self::DeleteGroup { id, name } => write!(f, "- delete group '{}' ({})", name, id),
+            Self::CreateScene { name, group, .. } => {
+                write!(f, "+ create scene '{}' in group {}", name, group)
+            }
+            Self::SetScene { id, name, .. } => write!(f, "~ modify scene '{}' ({})", name, id),
+            Self::DeleteScene { id, name } => write!(f, "- delete scene '{}' ({})", name, id),
+            Self::CreateSchedule { name, .. } => write!(f, "+ create schedule '{}'", name),
+            Self::SetSchedule { id, name, .. } => {
+                write!(f, "~ modify schedule '{}' ({})", name, id)
+            }
+            Self::DeleteSchedule { id, name } => {
+                write!(f, "- delete schedule '{}' ({})", name, id)
+            }
+        }
+    }
+}
+
+/// An ordered list of operations that brings a bridge into the desired state.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Plan {
+    /// Operations in the order they are applied.
+    pub operations: Vec<Operation>,
+}
+
+impl Plan {
+    /// Whether the bridge is already in the desired state.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Applies the operations of the plan to a bridge.
+    ///
+    /// Identifiers of groups and scenes that are created by the plan are resolved when they are
+    /// needed by a later operation. The plan stops at the first operation that fails.
+    pub fn apply(&self, bridge: &Bridge) -> Result<()> {
+        let mut created = Created::default();
+        for operation in &self.operations {
+            match operation {
+                Operation::CreateGroup { name, creator } => {
+                    let id = bridge.create_group(creator)?;
+                    created.insert(LinkKind::Group, name, id);
+                }
+                Operation::SetGroup { id, modifier, .. } => {
+                    bridge.set_group_attribute(id, modifier)?;
+                }
+                Operation::DeleteGroup { id, .. } => bridge.delete_group(id)?,
+                Operation::CreateScene {
+                    name,
+                    group,
+                    creator,
+                } => {
+                    let group = created.resolve(LinkKind::Group, group)?;
+                    let id = bridge.create_scene(&creator.clone().group(group))?;
+                    created.insert(LinkKind::Scene, name, id);
+                }
+                Operation::SetScene { id, modifier, .. } => {
+                    bridge.set_scene(id, modifier)?;
+                }
+                Operation::DeleteScene { id, .. } => bridge.delete_scene(id)?,
+                Operation::CreateSchedule {
+                    name,
+                    description,
+                    local_time,
+                    status,
+                    action,
+                } => {
+                    let action = action.resolve(&bridge.username, &created)?;
+                    let creator = schedule::Creator::new(action, local_time.clone())
+                        .name(name)
+                        .description(description)
+                        .status(status.clone());
+                    bridge.create_schedule(&creator)?;
+                }
+                Operation::SetSchedule {
+                    id,
+                    action,
+                    modifier,
+                    ..
+                } => {
+                    let mut modifier = modifier.clone();
+                    if let Some(v) = action {
+                        modifier = modifier.action(v.resolve(&bridge.username, &created)?);
+                    }
+                    bridge.set_schedule(id, &modifier)?;
+                }
+                Operation::DeleteSchedule { id, .. } => bridge.delete_schedule(id)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for operation in &self.operations {
+            writeln!(f, "{}", operation)?;
+        }
+        Ok(())
+    }
+}
+
+/// App data that marks scenes as managed, if no other app data is set.
+pub const DEFAULT_APP_DATA: &str = "huelib-reconcile";
+
+/// Compares a desired state with the resources of a bridge.
+///
+/// Only resources that are managed by the reconciler are matched, modified or deleted. A group or
+/// schedule is managed if its name starts with the name prefix, so a name prefix is required for
+/// documents with rooms, zones or schedules. A scene is managed if its name starts with the name
+/// prefix or if its app data equals the app data of the reconciler, which defaults to
+/// [`DEFAULT_APP_DATA`].
+///
+/// [`DEFAULT_APP_DATA`]: constant.DEFAULT_APP_DATA.html
+///
+/// # Examples
+///
+/// Print the plan for a bridge and apply it.
+/// ```no_run
+/// use huelib::reconcile::{Document, Reconciler};
+/// use huelib::Bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+/// let document: Document = serde_json::from_str(r#"{"rooms": []}"#).unwrap();
+/// let reconciler = Reconciler::new(document).name_prefix("[managed] ");
+///
+/// let plan = reconciler.plan(&bridge).unwrap();
+/// print!("{}", plan);
+/// plan.apply(&bridge).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reconciler {
+    document: Document,
+    name_prefix: String,
+    app_data: String,
+}
+
+impl Reconciler {
+    /// Creates a new reconciler for a desired state.
+    pub fn new(document: Document) -> Self {
+        Self {
+            document,
+            name_prefix: String::new(),
+            app_data: DEFAULT_APP_DATA.to_owned(),
+        }
+    }
+
+    /// Sets the prefix of the names of managed resources.
+    ///
+    /// The prefix is prepended to the names of the document when resources are created.
+    pub fn name_prefix(mut self, value: impl Into<String>) -> Self {
+        self.name_prefix = value.into();
+        self
+    }
+
+    /// Sets the app data that marks scenes as managed.
+    ///
+    /// The app data is set on every scene that is created. Defaults to [`DEFAULT_APP_DATA`].
+    ///
+    /// [`DEFAULT_APP_DATA`]: constant.DEFAULT_APP_DATA.html
+    pub fn app_data(mut self, value: impl Into<String>) -> Self {
+        self.app_data = value.into();
+        self
+    }
+
+    /// Compares the desired state with the bridge and returns the operations to reconcile them.
+    ///
+    /// Operations are ordered so that schedules, scenes and groups are deleted first, followed by
+    /// the creation and modification of groups, scenes and schedules.
+    ///
+    /// Fails with [`ReconcileError::MissingNamePrefix`] if the document contains rooms, zones or
+    /// schedules but no name prefix is set, and with [`ReconcileError::LockedScene`] if a scene
+    /// has to be deleted while a rule or a schedule that is not deleted uses it.
+    ///
+    /// [`ReconcileError::MissingNamePrefix`]: enum.ReconcileError.html#variant.MissingNamePrefix
+    /// [`ReconcileError::LockedScene`]: enum.ReconcileError.html#variant.LockedScene
+    pub fn plan(&self, bridge: &Bridge) -> Result<Plan> {
+        let needs_prefix = !self.document.rooms.is_empty()
+            || !self.document.zones.is_empty()
+            || !self.document.schedules.is_empty();
+        if needs_prefix && self.name_prefix.is_empty() {
+            return Err(ReconcileError::MissingNamePrefix.into());
+        }
+        let lights = bridge.get_all_lights()?;
+        let groups = bridge.get_all_groups()?;
+        let scenes = bridge.get_all_scenes()?;
+        let schedules = bridge.get_all_schedules()?;
+        let mut deletes = Vec::new();
+        let mut operations = Vec::new();
+
+        let mut group_targets = HashMap::new();
+        let mut matched = Vec::new();
+        let desired_groups = self
+            .document
+            .rooms
+            .iter()
+            .map(|v| (group::CreatableKind::Room, v))
+            .chain(
+                self.document
+                    .zones
+                    .iter()
+                    .map(|v| (group::CreatableKind::Zone, v)),
+            );
+        for (kind, spec) in desired_groups {
+            let name = self.tagged(&spec.name);
+            let light_ids = resolve_lights(&lights, &spec.lights)?;
+            let existing = groups.iter().find(|v| {
+                v.name == name && v.kind == group::Kind::Creatable(kind) && self.has_prefix(&v.name)
+            });
+            match existing {
+                Some(group) => {
+                    matched.push(group.id.clone());
+                    group_targets.insert(spec.name.clone(), Target::Existing(group.id.to_string()));
+                    let mut modifier = group::AttributeModifier::new();
+                    if !same_set(&group.lights, &light_ids) {
+                        modifier = modifier.lights(light_ids);
+                    }
+                    if let Some(v) = &spec.class {
+                        if group.class.as_ref() != Some(v) {
+                            modifier = modifier.class(v.clone());
+                        }
+                    }
+                    if !modifier.is_empty() {
+                        operations.push(Operation::SetGroup {
+                            id: group.id.clone(),
+                            name,
+                            modifier,
+                        });
+                    }
+                }
+                None => {
+                    let mut creator = group::Creator::new(&name, light_ids).kind(kind);
+                    if let Some(v) = &spec.class {
+                        creator = creator.class(v.clone());
+                    }
+                    group_targets.insert(spec.name.clone(), Target::Planned(name.clone()));
+                    operations.push(Operation::CreateGroup { name, creator });
+                }
+            }
+        }
+        for group in &groups {
+            let is_room_or_zone = group.kind == group::Kind::Creatable(group::CreatableKind::Room)
+                || group.kind == group::Kind::Creatable(group::CreatableKind::Zone);
+            if is_room_or_zone && self.has_prefix(&group.name) && !matched.contains(&group.id) {
+                deletes.push(Operation::DeleteGroup {
+                    id: group.id.clone(),
+                    name: group.name.clone(),
+                });
+            }
+        }
+        let group_target = |name: &str| -> Result<Target> {
+            if let Some(v) = group_targets.get(name) {
+                return Ok(v.clone());
+            }
+            match groups.iter().find(|v| v.name == name) {
+                Some(v) => Ok(Target::Existing(v.id.to_string())),
+                None => Err(ReconcileError::UnknownReference(format!("group '{}'", name)).into()),
+            }
+        };
+
+        let mut scene_targets = HashMap::new();
+        let mut matched = Vec::new();
+        let mut scene_deletes = Vec::new();
+        for spec in &self.document.scenes {
+            let name = self.tagged(&spec.name);
+            let group = group_target(&spec.group)?;
+            let light_names: Vec<String> = spec.lights.keys().cloned().collect();
+            let light_ids = resolve_lights(&lights, &light_names)?;
+            let existing = scenes
+                .iter()
+                .find(|v| v.name == name && self.is_managed_scene(v));
+            if let Some(scene) = existing {
+                matched.push(scene.id.clone());
+                if Target::Existing(scene.group.clone().unwrap_or_default().into_string()) == group
+                {
+                    scene_targets.insert(spec.name.clone(), Target::Existing(scene.id.to_string()));
+                    let current = bridge
+                        .get_scene(&scene.id)?
+                        .light_states
+                        .unwrap_or_default();
+                    let mut modifier = scene::Modifier::new();
+                    if !same_set(scene.lights.as_deref().unwrap_or_default(), &light_ids) {
+                        modifier = modifier.lights(light_ids.clone());
+                    }
+                    for (id, state) in light_ids.iter().zip(spec.lights.values()) {
+                        if !current.get(id).is_some_and(|v| is_subset(state, v)) {
+                            modifier = modifier.light_state(id, state.clone());
+                        }
+                    }
+                    if !modifier.is_empty() {
+                        operations.push(Operation::SetScene {
+                            id: scene.id.clone(),
+                            name,
+                            modifier,
+                        });
+                    }
+                    continue;
+                }
+                // The group of a scene cannot be modified, so the scene is created again.
+                scene_deletes.push(Operation::DeleteScene {
+                    id: scene.id.clone(),
+                    name: scene.name.clone(),
+                });
+            }
+            let mut creator = scene::Creator::new(&name, light_ids.clone())
+                .kind(scene::Kind::GroupScene)
+                .app_data(&self.app_data);
+            for (id, state) in light_ids.iter().zip(spec.lights.values()) {
+                creator = creator.light_state(id, state.clone());
+            }
+            scene_targets.insert(spec.name.clone(), Target::Planned(name.clone()));
+            operations.push(Operation::CreateScene {
+                name,
+                group,
+                creator,
+            });
+        }
+        for scene in &scenes {
+            if self.is_managed_scene(scene) && !matched.contains(&scene.id) {
+                scene_deletes.push(Operation::DeleteScene {
+                    id: scene.id.clone(),
+                    name: scene.name.clone(),
+                });
+            }
+        }
+
+        let mut matched = Vec::new();
+        let mut schedule_deletes = Vec::new();
+        for spec in &self.document.schedules {
+            let name = self.tagged(&spec.name);
+            let mut body = spec.body.clone();
+            let scene = match body.remove("scene") {
+                Some(JsonValue::String(v)) => match scene_targets.get(&v) {
+                    Some(v) => Some(v.clone()),
+                    None => match scenes.iter().find(|s| s.name == v) {
+                        Some(s) => Some(Target::Existing(s.id.to_string())),
+                        None => {
+                            let error = ReconcileError::UnknownReference(format!("scene '{}'", v));
+                            return Err(error.into());
+                        }
+                    },
+                },
+                Some(v) => {
+                    body.insert("scene".to_owned(), v);
+                    None
+                }
+                None => None,
+            };
+            let action = PlannedAction {
+                group: group_target(&spec.group)?,
+                scene,
+                body,
+            };
+            let status = if spec.enabled {
+                schedule::Status::Enabled
+            } else {
+                schedule::Status::Disabled
+            };
+            match schedules
+                .iter()
+                .find(|v| v.name == name && self.has_prefix(&v.name))
+            {
+                Some(schedule) => {
+                    matched.push(schedule.id.clone());
+                    let mut modifier = schedule::Modifier::new();
+                    if schedule.description != spec.description {
+                        modifier = modifier.description(&spec.description);
+                    }
+                    if schedule.local_time != spec.local_time {
+                        modifier = modifier.localtime(&spec.local_time);
+                    }
+                    if schedule.status != status {
+                        modifier = modifier.status(status);
+                    }
+                    let action = if action.matches(&schedule.action) {
+                        None
+                    } else {
+                        Some(action)
+                    };
+                    if !modifier.is_empty() || action.is_some() {
+                        operations.push(Operation::SetSchedule {
+                            id: schedule.id.clone(),
+                            name,
+                            action,
+                            modifier,
+                        });
+                    }
+                }
+                None => operations.push(Operation::CreateSchedule {
+                    name,
+                    description: spec.description.clone(),
+                    local_time: spec.local_time.clone(),
+                    status,
+                    action,
+                }),
+            }
+        }
+        for schedule in &schedules {
+            if self.has_prefix(&schedule.name) && !matched.contains(&schedule.id) {
+                schedule_deletes.push(Operation::DeleteSchedule {
+                    id: schedule.id.clone(),
+                    name: schedule.name.clone(),
+                });
+            }
+        }
+
+        let rules = bridge.get_all_rules()?;
+        for operation in &scene_deletes {
+            if let Operation::DeleteScene { id, name } = operation {
+                let deleted = |v: &resource::ScheduleId| {
+                    schedule_deletes.iter().any(|d| match d {
+                        Operation::DeleteSchedule { id, .. } => id == v,
+                        _ => false,
+                    })
+                };
+                let locked_by_schedule = schedules
+                    .iter()
+                    .any(|v| !deleted(&v.id) && uses_scene(&v.action, id.as_str()));
+                let locked_by_rule = rules
+                    .iter()
+                    .any(|v| v.actions.iter().any(|a| uses_scene(a, id.as_str())));
+                if locked_by_schedule || locked_by_rule {
+                    return Err(ReconcileError::LockedScene(name.clone()).into());
+                }
+            }
+        }
+
+        let mut plan = Plan::default();
+        plan.operations.extend(schedule_deletes);
+        plan.operations.extend(scene_deletes);
+        plan.operations.extend(deletes);
+        plan.operations.extend(operations);
+        Ok(plan)
+    }
+
+    fn tagged(&self, name: &str) -> String {
+        format!("{}{}", self.name_prefix, name)
+    }
+
+    fn has_prefix(&self, name: &str) -> bool {
+        !self.name_prefix.is_empty() && name.starts_with(&self.name_prefix)
+    }
+
+    fn is_managed_scene(&self, scene: &crate::resource::Scene) -> bool {
+        scene.app_data.data.as_ref() == Some(&self.app_data) || self.has_prefix(&scene.name)
+    }
+}
+
+/// Errors that can occur while planning the reconciliation of a bridge.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ReconcileError {
+    /// The reconciler has no name prefix to mark groups and schedules as managed.
+    #[error("A name prefix is required to manage groups and schedules")]
+    MissingNamePrefix,
+    /// A scene cannot be deleted, because a rule or schedule uses it.
+    #[error("Scene is locked by a rule or schedule: {0}")]
+    LockedScene(String),
+    /// The document references a resource that does not exist.
+    #[error("Unknown {0}")]
+    UnknownReference(String),
+}
+
+/// Identifiers of resources that were created while applying a plan.
+#[derive(Default)]
+struct Created {
+    ids: HashMap<(LinkKind, String), String>,
+}
+
+impl Created {
+    fn insert(&mut self, kind: LinkKind, name: &str, id: impl Into<String>) {
+        self.ids.insert((kind, name.to_owned()), id.into());
+    }
+
+    fn resolve(&self, kind: LinkKind, target: &Target) -> Result<String> {
+        match target {
+            Target::Existing(v) => Ok(v.clone()),
+            Target::Planned(v) => self
+                .ids
+                .get(&(kind.clone(), v.clone()))
+                .cloned()
+                .ok_or_else(|| {
+                    ReconcileError::UnknownReference(format!("{} '{}'", kind, v)).into()
+                }),
+        }
+    }
+}
+
+/// Resolves names or identifiers of lights to identifiers.
+fn resolve_lights(lights: &[Light], names: &[String]) -> Result<Vec<resource::LightId>> {
+    names
+        .iter()
+        .map(|name| {
+            lights
+                .iter()
+                .find(|v| &v.name == name)
+                .or_else(|| lights.iter().find(|v| &v.id == name))
+                .map(|v| v.id.clone())
+                .ok_or_else(|| ReconcileError::UnknownReference(format!("light '{}'", name)).into())
+        })
+        .collect()
+}
+
+fn same_set<T: Clone + Ord>(a: &[T], b: &[T]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort();
+    b.sort();
+    a == b
+}
+
+/// Whether every attribute of the desired light state equals the current light state.
+///
+/// Color coordinates are compared with the precision the bridge returns them with.
+fn is_subset(desired: &scene::LightStateModifier, current: &scene::LightStateModifier) -> bool {
+    let desired = serde_json::to_value(desired).unwrap_or_default();
+    let current = serde_json::to_value(current).unwrap_or_default();
+    let (desired, current) = match (desired.as_object(), current.as_object()) {
+        (Some(d), Some(c)) => (d.clone(), c.clone()),
+        _ => return false,
+    };
+    desired.iter().all(|(key, value)| match current.get(key) {
+        Some(v) if key == "xy" => {
+            let parse = |v: &JsonValue| serde_json::from_value::<(f32, f32)>(v.clone()).ok();
+            match (parse(value), parse(v)) {
+                (Some(d), Some(c)) => (d.0 - c.0).abs() < 1e-3 && (d.1 - c.1).abs() < 1e-3,
+                _ => false,
+            }
+        }
+        Some(v) => v == value,
+        None => false,
+    })
+}
+
+/// Whether an action recalls or modifies a scene.
+fn uses_scene(action: &Action, id: &str) -> bool {
+    let recalls = action.body.get("scene").and_then(|v| v.as_str()) == Some(id);
+    let mut parts = strip_username(&action.address).split('/');
+    recalls || (parts.next() == Some("scenes") && parts.next() == Some(id))
+}
+
+/// Removes the API path and username from the beginning of an address.
+fn strip_username(address: &str) -> &str {
+    let trimmed = address.trim_start_matches('/');
+    if trimmed.starts_with("api/") {
+        let mut parts = trimmed.splitn(3, '/');
+        parts.nth(2).unwrap_or_default()
+    } else {
+        trimmed
+    }
+}
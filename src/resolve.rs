@@ -0,0 +1,494 @@
+use crate::resource::{group, Datastore, Group, Light, LightId, Scene, Sensor};
+use crate::{Bridge, Result};
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+use thiserror::Error as ThisError;
+
+/// Pattern that is matched against the name of a resource.
+#[derive(Clone, Debug)]
+pub enum NamePattern {
+    /// Matches names that are equal to the value.
+    Exact(String),
+    /// Matches names that are equal to the value, ignoring the case of ASCII characters.
+    IgnoreCase(String),
+    /// Matches names with a glob pattern, ignoring the case of ASCII characters.
+    ///
+    /// The wildcard `*` matches any number of characters and `?` matches a single character.
+    Glob(String),
+    /// Matches names with a regular expression.
+    Regex(Regex),
+}
+
+impl NamePattern {
+    /// Creates a pattern from a regular expression.
+    pub fn regex(value: &str) -> StdResult<Self, regex::Error> {
+        Ok(Self::Regex(Regex::new(value)?))
+    }
+
+    /// Returns whether a name matches the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resolve::NamePattern;
+    ///
+    /// assert!(NamePattern::IgnoreCase("desk lamp".into()).matches("Desk Lamp"));
+    /// assert!(NamePattern::Glob("desk*".into()).matches("Desk Lamp"));
+    /// assert!(!NamePattern::Exact("Desk".into()).matches("Desk Lamp"));
+    /// assert!(NamePattern::regex(r"^Desk \w+$").unwrap().matches("Desk Lamp"));
+    /// ```
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Exact(v) => name == v,
+            Self::IgnoreCase(v) => name.eq_ignore_ascii_case(v),
+            Self::Glob(v) => glob_matches(v, name),
+            Self::Regex(v) => v.is_match(name),
+        }
+    }
+
+    /// Returns the resources whose identifier or, if no identifier matches, whose name matches
+    /// the pattern.
+    fn matching_resources<'a, T: 'a>(
+        &self,
+        resources: impl Iterator<Item = &'a T> + Clone,
+        name: impl Fn(&T) -> &str,
+        id: impl Fn(&T) -> &str,
+    ) -> Vec<&'a T> {
+        let by_id: Vec<&T> = resources.clone().filter(|v| self.is_id(id(v))).collect();
+        if !by_id.is_empty() {
+            return by_id;
+        }
+        resources.filter(|v| self.matches(name(v))).collect()
+    }
+
+    /// Returns whether the pattern is not a glob or regular expression and equals an identifier.
+    fn is_id(&self, id: &str) -> bool {
+        match self {
+            Self::Exact(v) | Self::IgnoreCase(v) => v == id,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for NamePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Exact(v) | Self::IgnoreCase(v) | Self::Glob(v) => f.write_str(v),
+            Self::Regex(v) => write!(f, "/{}/", v),
+        }
+    }
+}
+
+/// Parses a pattern of a selector.
+///
+/// Values enclosed in slashes are regular expressions, values with `*` or `?` are glob patterns
+/// and other values are compared ignoring the case.
+impl FromStr for NamePattern {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        if s.is_empty() {
+            Err(SelectorError::Empty)
+        } else if s.len() > 1 && s.starts_with('/') && s.ends_with('/') {
+            Ok(Self::regex(&s[1..s.len() - 1])?)
+        } else if s.contains(['*', '?']) {
+            Ok(Self::Glob(s.to_owned()))
+        } else {
+            Ok(Self::IgnoreCase(s.to_owned()))
+        }
+    }
+}
+
+/// Matches a glob pattern against a value, ignoring the case of ASCII characters.
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '?' || c.eq_ignore_ascii_case(&value[v]) => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bv)) => {
+                    backtrack = Some((bp, bv + 1));
+                    p = bp + 1;
+                    v = bv + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Errors that can occur while parsing a selector.
+#[derive(Clone, Debug, ThisError)]
+pub enum SelectorError {
+    /// The pattern of the selector is empty.
+    #[error("Selector pattern is empty")]
+    Empty,
+    /// The regular expression of the selector is invalid.
+    #[error("Invalid regular expression: {0}")]
+    Regex(#[from] regex::Error),
+}
+
+/// Errors that can occur while resolving resources by name.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ReferenceError {
+    /// No resource is referenced by the name or selector.
+    #[error("Unknown {0}")]
+    Unknown(String),
+    /// The name refers to more than one resource.
+    #[error("Ambiguous {0}")]
+    Ambiguous(String),
+}
+
+/// Expression that selects a set of lights.
+///
+/// Selectors are written as `<kind>:<pattern>`, for example `room:Kitchen`, `light:Desk*`,
+/// `group:3` or `type:Extended color light`. Without a kind, the pattern selects lights. The text
+/// before the first `:` is only treated as kind if it is one of `light`, `group`, `room`, `zone`,
+/// `scene` or `type`, otherwise the whole selector is a pattern for lights. The pattern is parsed
+/// as described in [`NamePattern::from_str`].
+///
+/// Patterns that are compared ignoring the case also match the identifier of lights, groups and
+/// scenes. If a resource has the pattern as identifier, only resources with that identifier are
+/// selected, so `light:2` selects the light with the identifier `2` and not a light named `2`.
+///
+/// [`NamePattern::from_str`]: enum.NamePattern.html#impl-FromStr
+///
+/// # Examples
+///
+/// ```
+/// use huelib::resolve::{NamePattern, Selector};
+///
+/// let selector: Selector = "light:Desk*".parse().unwrap();
+/// assert!(matches!(selector, Selector::Light(NamePattern::Glob(_))));
+/// assert_eq!(selector.to_string(), "light:Desk*");
+///
+/// let selector: Selector = "type:/^Extended/".parse().unwrap();
+/// assert!(matches!(selector, Selector::Type(NamePattern::Regex(_))));
+///
+/// let selector: Selector = "Desk: left".parse().unwrap();
+/// assert!(matches!(selector, Selector::Light(NamePattern::IgnoreCase(v)) if v == "Desk: left"));
+///
+/// let selector: Selector = "/^a:b/".parse().unwrap();
+/// assert!(matches!(selector, Selector::Light(NamePattern::Regex(_))));
+/// ```
+#[derive(Clone, Debug)]
+pub enum Selector {
+    /// Selects lights by name or identifier.
+    Light(NamePattern),
+    /// Selects the lights of groups of any kind by name or identifier.
+    ///
+    /// The group with the identifier `0` selects all lights.
+    Group(NamePattern),
+    /// Selects the lights of rooms by name or identifier.
+    Room(NamePattern),
+    /// Selects the lights of zones by name or identifier.
+    Zone(NamePattern),
+    /// Selects the lights of scenes by name or identifier.
+    Scene(NamePattern),
+    /// Selects lights by their type, for example `Extended color light`.
+    Type(NamePattern),
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let (kind, pattern) = match s.find(':') {
+            Some(i) => (s[..i].to_ascii_lowercase(), &s[i + 1..]),
+            None => (String::new(), s),
+        };
+        let selector: fn(NamePattern) -> Self = match kind.as_str() {
+            "light" => Self::Light,
+            "group" => Self::Group,
+            "room" => Self::Room,
+            "zone" => Self::Zone,
+            "scene" => Self::Scene,
+            "type" => Self::Type,
+            _ => return Ok(Self::Light(s.parse()?)),
+        };
+        Ok(selector(pattern.parse()?))
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Light(v) => write!(f, "light:{}", v),
+            Self::Group(v) => write!(f, "group:{}", v),
+            Self::Room(v) => write!(f, "room:{}", v),
+            Self::Zone(v) => write!(f, "zone:{}", v),
+            Self::Scene(v) => write!(f, "scene:{}", v),
+            Self::Type(v) => write!(f, "type:{}", v),
+        }
+    }
+}
+
+/// Finds lights, groups, scenes and sensors of a bridge by name and expands selectors.
+///
+/// The resolver works on a snapshot of the resources, so that many names can be resolved with
+/// a few requests.
+///
+/// # Examples
+///
+/// Turn on all lights in the kitchen.
+/// ```no_run
+/// use huelib::resolve::Resolver;
+/// use huelib::resource::{light, Modifier};
+/// use huelib::Bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+/// let resolver = Resolver::from_bridge(&bridge).unwrap();
+/// let modifier = light::StateModifier::new().on(true);
+/// for id in resolver.select(&"room:Kitchen".parse().unwrap()).unwrap() {
+///     bridge.set_light_state(id, &modifier).unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resolver {
+    lights: Vec<Light>,
+    groups: Vec<Group>,
+    scenes: Vec<Scene>,
+    sensors: Vec<Sensor>,
+}
+
+impl Resolver {
+    /// Creates a new resolver from resources.
+    pub fn new(
+        lights: Vec<Light>,
+        groups: Vec<Group>,
+        scenes: Vec<Scene>,
+        sensors: Vec<Sensor>,
+    ) -> Self {
+        Self {
+            lights,
+            groups,
+            scenes,
+            sensors,
+        }
+    }
+
+    /// Creates a new resolver with the current resources of a bridge.
+    pub fn from_bridge(bridge: &Bridge) -> Result<Self> {
+        Ok(Self::new(
+            bridge.get_all_lights()?,
+            bridge.get_all_groups()?,
+            bridge.get_all_scenes()?,
+            bridge.get_all_sensors()?,
+        ))
+    }
+
+    /// Returns the lights whose identifier or name matches a pattern.
+    ///
+    /// Patterns that are compared ignoring the case also match identifiers. If a light has the
+    /// pattern as identifier, only that light is returned, like with [`select`].
+    ///
+    /// [`select`]: #method.select
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resolve::{NamePattern, Resolver};
+    /// use huelib::resource::Light;
+    ///
+    /// let json = r#"{"name": "Desk", "type": "Dimmable light", "state": {"reachable": true},
+    ///     "modelid": "LWB010", "uniqueid": "00:17", "swversion": "1.0",
+    ///     "swupdate": {"state": "noupdates", "lastinstall": null},
+    ///     "config": {"archetype": "classicbulb", "function": "functional", "direction": ""},
+    ///     "capabilities": {"certified": true, "control": {},
+    ///     "streaming": {"renderer": false, "proxy": false}}}"#;
+    /// let mut desk: Light = serde_json::from_str(json).unwrap();
+    /// desk.id = "2".into();
+    /// let mut other = desk.clone();
+    /// other.id = "3".into();
+    /// other.name = "2".into();
+    /// let resolver = Resolver::new(vec![desk, other], Vec::new(), Vec::new(), Vec::new());
+    ///
+    /// let lights = resolver.lights(&NamePattern::IgnoreCase("2".into()));
+    /// assert_eq!(lights.len(), 1);
+    /// assert_eq!(lights[0].name, "Desk");
+    /// assert_eq!(resolver.lights(&NamePattern::Glob("2*".into()))[0].name, "2");
+    /// ```
+    pub fn lights(&self, pattern: &NamePattern) -> Vec<&Light> {
+        pattern.matching_resources(self.lights.iter(), |v| &v.name, |v| v.id.as_str())
+    }
+
+    /// Returns the groups whose identifier or name matches a pattern.
+    ///
+    /// See [`lights`] for how identifiers are matched.
+    ///
+    /// [`lights`]: #method.lights
+    pub fn groups(&self, pattern: &NamePattern) -> Vec<&Group> {
+        pattern.matching_resources(self.groups.iter(), |v| &v.name, |v| v.id.as_str())
+    }
+
+    /// Returns the scenes whose identifier or name matches a pattern.
+    ///
+    /// See [`lights`] for how identifiers are matched.
+    ///
+    /// [`lights`]: #method.lights
+    pub fn scenes(&self, pattern: &NamePattern) -> Vec<&Scene> {
+        pattern.matching_resources(self.scenes.iter(), |v| &v.name, |v| v.id.as_str())
+    }
+
+    /// Returns the sensors whose identifier or name matches a pattern.
+    ///
+    /// See [`lights`] for how identifiers are matched.
+    ///
+    /// [`lights`]: #method.lights
+    pub fn sensors(&self, pattern: &NamePattern) -> Vec<&Sensor> {
+        pattern.matching_resources(self.sensors.iter(), |v| &v.name, |v| v.id.as_str())
+    }
+
+    /// Returns the light with a name.
+    ///
+    /// A light whose name is equal to the given name is preferred over a light whose name only
+    /// differs in case.
+    pub fn light(&self, name: &str) -> Result<&Light> {
+        find_one("light", name, &self.lights, |v| &v.name)
+    }
+
+    /// Returns the group with a name.
+    ///
+    /// See [`light`] for how the name is compared.
+    ///
+    /// [`light`]: #method.light
+    pub fn group(&self, name: &str) -> Result<&Group> {
+        find_one("group", name, &self.groups, |v| &v.name)
+    }
+
+    /// Returns the scene with a name.
+    ///
+    /// See [`light`] for how the name is compared.
+    ///
+    /// [`light`]: #method.light
+    pub fn scene(&self, name: &str) -> Result<&Scene> {
+        find_one("scene", name, &self.scenes, |v| &v.name)
+    }
+
+    /// Returns the sensor with a name.
+    ///
+    /// See [`light`] for how the name is compared.
+    ///
+    /// [`light`]: #method.light
+    pub fn sensor(&self, name: &str) -> Result<&Sensor> {
+        find_one("sensor", name, &self.sensors, |v| &v.name)
+    }
+
+    /// Returns the identifiers of the lights that are selected by a selector.
+    ///
+    /// Returns an error if the selector does not match any resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resolve::Resolver;
+    /// use huelib::resource::{Group, LightId};
+    ///
+    /// let json = r#"{"name": "Kitchen", "lights": ["1", "2"], "sensors": [], "type": "Room",
+    ///     "class": "Kitchen", "state": {"all_on": false, "any_on": true}, "recycle": false}"#;
+    /// let group: Group = serde_json::from_str(json).unwrap();
+    /// let resolver = Resolver::new(Vec::new(), vec![group], Vec::new(), Vec::new());
+    ///
+    /// let ids = resolver.select(&"room:kitchen".parse().unwrap()).unwrap();
+    /// let expected: Vec<LightId> = vec!["1".into(), "2".into()];
+    /// assert_eq!(ids.into_iter().collect::<Vec<_>>(), expected);
+    /// assert!(resolver.select(&"zone:Kitchen".parse().unwrap()).is_err());
+    /// ```
+    pub fn select(&self, selector: &Selector) -> Result<BTreeSet<LightId>> {
+        let mut matched = false;
+        let mut ids = BTreeSet::new();
+        match selector {
+            Selector::Light(v) => {
+                for light in
+                    v.matching_resources(self.lights.iter(), |l| &l.name, |l| l.id.as_str())
+                {
+                    matched = true;
+                    ids.insert(light.id.clone());
+                }
+            }
+            Selector::Type(v) => {
+                for light in self.lights.iter().filter(|l| v.matches(&l.kind)) {
+                    matched = true;
+                    ids.insert(light.id.clone());
+                }
+            }
+            Selector::Group(v) if v.is_id("0") => {
+                matched = true;
+                ids.extend(self.lights.iter().map(|l| l.id.clone()));
+            }
+            Selector::Group(v) | Selector::Room(v) | Selector::Zone(v) => {
+                let kind = match selector {
+                    Selector::Room(_) => Some(group::CreatableKind::Room),
+                    Selector::Zone(_) => Some(group::CreatableKind::Zone),
+                    _ => None,
+                };
+                let groups = self
+                    .groups
+                    .iter()
+                    .filter(|g| kind.is_none_or(|k| g.kind == group::Kind::Creatable(k)));
+                for group in v.matching_resources(groups, |g| &g.name, |g| g.id.as_str()) {
+                    matched = true;
+                    ids.extend(group.lights.iter().cloned());
+                }
+            }
+            Selector::Scene(v) => {
+                for scene in
+                    v.matching_resources(self.scenes.iter(), |s| &s.name, |s| s.id.as_str())
+                {
+                    matched = true;
+                    ids.extend(scene.lights.iter().flatten().cloned());
+                }
+            }
+        }
+        if !matched {
+            return Err(ReferenceError::Unknown(selector.to_string()).into());
+        }
+        Ok(ids)
+    }
+}
+
+impl From<Datastore> for Resolver {
+    fn from(value: Datastore) -> Self {
+        Self::new(value.lights, value.groups, value.scenes, value.sensors)
+    }
+}
+
+/// Finds the only resource with a name, preferring exact matches over case-insensitive matches.
+fn find_one<'a, T>(
+    kind: &str,
+    name: &str,
+    resources: &'a [T],
+    resource_name: impl Fn(&T) -> &str,
+) -> Result<&'a T> {
+    for pattern in &[
+        NamePattern::Exact(name.to_owned()),
+        NamePattern::IgnoreCase(name.to_owned()),
+    ] {
+        let mut found = resources
+            .iter()
+            .filter(|v| pattern.matches(resource_name(v)));
+        match (found.next(), found.next()) {
+            (Some(v), None) => return Ok(v),
+            (Some(_), Some(_)) => {
+                return Err(ReferenceError::Ambiguous(format!("{} '{}'", kind, name)).into())
+            }
+            (None, _) => {}
+        }
+    }
+    Err(ReferenceError::Unknown(format!("{} '{}'", kind, name)).into())
+}
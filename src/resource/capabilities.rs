@@ -1,8 +1,9 @@
 use crate::resource;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Capabilities of resources.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "capabilities::Capabilities")]
 pub struct Capabilities {
     /// Capabilities of the connected lights.
     pub lights: Info,
@@ -27,7 +28,7 @@ pub struct Capabilities {
 impl resource::Resource for Capabilities {}
 
 /// Info about the capability of a resource.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Info {
     /// Number of currently available resources.
     pub available: usize,
@@ -36,7 +37,7 @@ pub struct Info {
 }
 
 /// Info about the capability of sensors.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct SensorsInfo {
     /// Number of currently available sensors.
     pub available: usize,
@@ -51,7 +52,7 @@ pub struct SensorsInfo {
 }
 
 /// Info about the capability of scenes.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct ScenesInfo {
     /// Number of currently available scenes.
     pub available: usize,
@@ -63,7 +64,7 @@ pub struct ScenesInfo {
 }
 
 /// Info about the capability of rules.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct RulesInfo {
     /// Number of currently available rules.
     pub available: usize,
@@ -76,7 +77,7 @@ pub struct RulesInfo {
 }
 
 /// Info about the capability of scenes.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct StreamingInfo {
     /// Number of currently available client streams.
     pub available: usize,
@@ -87,7 +88,7 @@ pub struct StreamingInfo {
 }
 
 /// List of timezones.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Timezones {
     /// List of available timezones.
     pub values: Vec<String>,
@@ -1,10 +1,10 @@
 use crate::{resource, util};
-use serde::{de, Deserialize, Serialize};
-use serde_repr::Deserialize_repr;
+use serde::{de, ser, Deserialize, Serialize};
 use std::net::IpAddr;
 
 /// Configuration for a bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "config::Config")]
 pub struct Config {
     /// Name of the bridge.
     pub name: String,
@@ -48,13 +48,17 @@ pub struct Config {
     #[serde(rename = "UTC")]
     pub current_time: chrono::NaiveDateTime,
     /// Local time of the bridge.
+    #[serde(rename = "localtime")]
     #[serde(
-        rename = "localtime",
-        deserialize_with = "util::deserialize_option_date_time"
+        deserialize_with = "util::deserialize_option_date_time",
+        serialize_with = "util::serialize_option_date_time"
     )]
     pub local_time: Option<chrono::NaiveDateTime>,
     /// Timezone of the bridge as OlsenIDs.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    #[serde(
+        deserialize_with = "util::deserialize_option_string",
+        serialize_with = "util::serialize_option_string"
+    )]
     pub timezone: Option<String>,
     /// The current wireless frequency channel used by the bridge.
     ///
@@ -84,7 +88,10 @@ pub struct Config {
     /// Backup information about the bridge.
     pub backup: Backup,
     /// Whitelisted users.
-    #[serde(deserialize_with = "deserialize_whitelist")]
+    #[serde(
+        deserialize_with = "deserialize_whitelist",
+        serialize_with = "serialize_whitelist"
+    )]
     pub whitelist: Vec<User>,
 }
 
@@ -101,8 +108,18 @@ fn deserialize_whitelist<'de, D: de::Deserializer<'de>>(
     Ok(users)
 }
 
+fn serialize_whitelist<S: ser::Serializer>(
+    users: &[User],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let map: std::collections::BTreeMap<&str, &User> =
+        users.iter().map(|v| (v.id.as_str(), v)).collect();
+    map.serialize(serializer)
+}
+
 /// Information about software updates.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "config::SoftwareUpdate")]
 pub struct SoftwareUpdate {
     /// State of software updates.
     pub state: SoftwareUpdateState,
@@ -121,7 +138,7 @@ pub struct SoftwareUpdate {
 }
 
 /// State of software updates.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SoftwareUpdateState {
     /// System does not know if new updates are available.
@@ -136,23 +153,27 @@ pub enum SoftwareUpdateState {
     AllReadyToInstall,
     /// System update is installing.
     Installing,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Configuration for automatically updating.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct SoftwareUpdateAutoInstall {
     /// Whether automatic updates are activated.
     pub on: bool,
     /// The time when updates are installed.
+    #[serde(rename = "updatetime")]
     #[serde(
-        rename = "updatetime",
-        deserialize_with = "util::deserialize_option_time"
+        deserialize_with = "util::deserialize_option_time",
+        serialize_with = "util::serialize_option_time"
     )]
     pub update_time: Option<chrono::NaiveTime>,
 }
 
 /// Portal state of the bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct PortalState {
     /// Signedon.
     pub signedon: bool,
@@ -165,7 +186,7 @@ pub struct PortalState {
 }
 
 /// Internet services of the bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct InternetServices {
     /// Whether the bridge is connected to the internet.
     pub internet: ServiceStatus,
@@ -180,17 +201,20 @@ pub struct InternetServices {
 }
 
 /// Status of a service.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ServiceStatus {
     /// The serivce is connected.
     Connected,
     /// The serivce is not connected.
     Disconnected,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Backup information about the bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Backup {
     /// Status of backup/restore.
     pub status: BackupStatus,
@@ -202,7 +226,7 @@ pub struct Backup {
 }
 
 /// Status of backup/restore.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum BackupStatus {
     /// No backup or restore ongoing.
     #[serde(rename = "idle")]
@@ -226,22 +250,48 @@ pub enum BackupStatus {
     /// Indicates that the bridge is in the process of restoring the backup file.
     #[serde(rename = "restoring")]
     Restoring,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Backup error of the bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize_repr)]
-#[repr(u8)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum BackupError {
     /// The backup has not detected an internal error.
-    None = 0,
+    None,
     /// Failed to export a backup.
-    ExportFailed = 1,
+    ExportFailed,
     /// Failed to import a backup.
-    ImportFailed = 2,
+    ImportFailed,
+    /// Value that is unknown to this library.
+    Unknown(u8),
+}
+
+impl Serialize for BackupError {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Self::None => 0,
+            Self::ExportFailed => 1,
+            Self::ImportFailed => 2,
+            Self::Unknown(v) => *v,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for BackupError {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match u8::deserialize(deserializer)? {
+            0 => Self::None,
+            1 => Self::ExportFailed,
+            2 => Self::ImportFailed,
+            v => Self::Unknown(v),
+        })
+    }
 }
 
 /// User of a bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct User {
     /// Identifier of the user.
     #[serde(skip)]
@@ -0,0 +1,547 @@
+use crate::resource::resourcelink::{Link, LinkKind};
+use crate::resource::{
+    self, rule, Action, Config, Group, Light, Resourcelink, Rule, Scene, Schedule, Sensor,
+};
+use serde::{de, ser, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+/// The full state of a bridge containing all resources.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Datastore {
+    /// Configuration of the bridge.
+    pub config: Config,
+    /// Lights that are connected to the bridge.
+    pub lights: Vec<Light>,
+    /// Groups of the bridge.
+    pub groups: Vec<Group>,
+    /// Scenes of the bridge.
+    pub scenes: Vec<Scene>,
+    /// Schedules of the bridge.
+    pub schedules: Vec<Schedule>,
+    /// Rules of the bridge.
+    pub rules: Vec<Rule>,
+    /// Sensors that are connected to the bridge.
+    pub sensors: Vec<Sensor>,
+    /// Resourcelinks of the bridge.
+    pub resourcelinks: Vec<Resourcelink>,
+}
+
+impl resource::Resource for Datastore {}
+
+impl<'de> Deserialize<'de> for Datastore {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct DatastoreJson {
+            config: Config,
+            lights: HashMap<String, Light>,
+            groups: HashMap<String, Group>,
+            scenes: HashMap<String, Scene>,
+            schedules: HashMap<String, Schedule>,
+            rules: HashMap<String, Rule>,
+            sensors: HashMap<String, Sensor>,
+            #[serde(default)]
+            resourcelinks: HashMap<String, Resourcelink>,
+        }
+
+        let value = DatastoreJson::deserialize(deserializer)?;
+        Ok(Self {
+            config: value.config,
+            lights: value
+                .lights
+                .into_iter()
+                .map(|(k, v)| v.with_id(k))
+                .collect(),
+            groups: value
+                .groups
+                .into_iter()
+                .map(|(k, v)| v.with_id(k))
+                .collect(),
+            scenes: value
+                .scenes
+                .into_iter()
+                .map(|(k, v)| v.with_id(k))
+                .collect(),
+            schedules: value
+                .schedules
+                .into_iter()
+                .map(|(k, v)| v.with_id(k))
+                .collect(),
+            rules: value.rules.into_iter().map(|(k, v)| v.with_id(k)).collect(),
+            sensors: value
+                .sensors
+                .into_iter()
+                .map(|(k, v)| v.with_id(k))
+                .collect(),
+            resourcelinks: value
+                .resourcelinks
+                .into_iter()
+                .map(|(k, v)| v.with_id(k))
+                .collect(),
+        })
+    }
+}
+
+impl Serialize for Datastore {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn by_id<T>(resources: &[T], id: impl Fn(&T) -> &str) -> BTreeMap<&str, &T> {
+            resources.iter().map(|v| (id(v), v)).collect()
+        }
+
+        #[derive(Serialize)]
+        struct DatastoreJson<'a> {
+            config: &'a Config,
+            lights: BTreeMap<&'a str, &'a Light>,
+            groups: BTreeMap<&'a str, &'a Group>,
+            scenes: BTreeMap<&'a str, &'a Scene>,
+            schedules: BTreeMap<&'a str, &'a Schedule>,
+            rules: BTreeMap<&'a str, &'a Rule>,
+            sensors: BTreeMap<&'a str, &'a Sensor>,
+            resourcelinks: BTreeMap<&'a str, &'a Resourcelink>,
+        }
+
+        DatastoreJson {
+            config: &self.config,
+            lights: by_id(&self.lights, |v| v.id.as_str()),
+            groups: by_id(&self.groups, |v| v.id.as_str()),
+            scenes: by_id(&self.scenes, |v| v.id.as_str()),
+            schedules: by_id(&self.schedules, |v| v.id.as_str()),
+            rules: by_id(&self.rules, |v| v.id.as_str()),
+            sensors: by_id(&self.sensors, |v| v.id.as_str()),
+            resourcelinks: by_id(&self.resourcelinks, |v| v.id.as_str()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Datastore {
+    /// Returns whether a resource of the given kind and identifier exists.
+    ///
+    /// The group with the identifier `0` always exists, since it contains all lights. Resources
+    /// of an unknown kind never exist.
+    pub fn contains(&self, kind: LinkKind, id: impl AsRef<str>) -> bool {
+        let id = id.as_ref();
+        match kind {
+            LinkKind::Group => id == "0" || self.groups.iter().any(|v| v.id == id),
+            LinkKind::Light => self.lights.iter().any(|v| v.id == id),
+            LinkKind::Resourcelink => self.resourcelinks.iter().any(|v| v.id == id),
+            LinkKind::Rule => self.rules.iter().any(|v| v.id == id),
+            LinkKind::Scene => self.scenes.iter().any(|v| v.id == id),
+            LinkKind::Schedule => self.schedules.iter().any(|v| v.id == id),
+            LinkKind::Sensor => self.sensors.iter().any(|v| v.id == id),
+            LinkKind::Unknown(_) => false,
+        }
+    }
+
+    /// Returns whether an address refers to an existing resource.
+    ///
+    /// The address can either be relative to the username (`/lights/1/state`) or contain the
+    /// full path of the API (`/api/<username>/lights/1/state`). Addresses of the configuration
+    /// (`/config/localtime`) are always valid.
+    pub fn contains_address(&self, address: impl AsRef<str>) -> bool {
+        let address = address.as_ref();
+        if address_parts(address).next() == Some("config") {
+            return true;
+        }
+        match parse_address(address) {
+            Some(v) => self.contains(v.kind, v.id),
+            None => false,
+        }
+    }
+
+    /// Returns the resources that are referenced by a rule, schedule or resourcelink.
+    ///
+    /// Rules and schedules reference the resources in the addresses of their conditions and
+    /// actions, and scenes that are recalled in the body of an action. Other kinds of resources
+    /// do not reference anything.
+    pub fn references(&self, link: &Link) -> Vec<Link> {
+        let mut references = Vec::new();
+        match link.kind {
+            LinkKind::Rule => {
+                if let Some(rule) = self.rules.iter().find(|v| v.id == link.id) {
+                    for condition in &rule.conditions {
+                        references.extend(parse_address(&condition.address));
+                    }
+                    for action in &rule.actions {
+                        references.extend(action_references(action));
+                    }
+                }
+            }
+            LinkKind::Schedule => {
+                if let Some(schedule) = self.schedules.iter().find(|v| v.id == link.id) {
+                    references.extend(action_references(&schedule.action));
+                }
+            }
+            LinkKind::Resourcelink => {
+                if let Some(resourcelink) = self.resourcelinks.iter().find(|v| v.id == link.id) {
+                    references.extend(resourcelink.links.iter().cloned());
+                }
+            }
+            _ => {}
+        }
+        let mut seen = HashSet::new();
+        references.retain(|v| seen.insert(v.clone()));
+        references
+    }
+
+    /// Returns the resourcelinks, rules and schedules that reference a resource.
+    ///
+    /// # Examples
+    ///
+    /// Print everything that depends on the light with the identifier `1`.
+    /// ```no_run
+    /// use huelib::resource::resourcelink::LinkKind;
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let datastore = bridge.get_datastore().unwrap();
+    /// for link in datastore.dependents(LinkKind::Light, "1") {
+    ///     println!("{}", link);
+    /// }
+    /// ```
+    pub fn dependents(&self, kind: LinkKind, id: impl AsRef<str>) -> Vec<Link> {
+        let target = Link {
+            kind,
+            id: id.as_ref().to_owned(),
+        };
+        self.dependent_candidates()
+            .filter(|v| self.references(v).contains(&target))
+            .collect()
+    }
+
+    /// Returns a plan to delete a resourcelink and every resource that it owns.
+    ///
+    /// Resources that are owned by the resourcelink are deleted recursively, unless they are also
+    /// referenced by a resourcelink, rule or schedule that is not deleted. Those resources are
+    /// listed in [`DeletePlan::kept`] with their dependents. Lights are never deleted. The
+    /// resources are ordered so that nothing is deleted while it is still referenced: the
+    /// resourcelinks first, followed by rules, schedules, scenes, sensors and groups.
+    ///
+    /// The plan can be inspected as a dry-run and executed with [`Bridge::execute_delete_plan`].
+    ///
+    /// [`DeletePlan::kept`]: struct.DeletePlan.html#structfield.kept
+    /// [`Bridge::execute_delete_plan`]: ../../bridge/struct.Bridge.html#method.execute_delete_plan
+    ///
+    /// # Examples
+    ///
+    /// Print the plan and delete the resourcelink with the identifier `1`.
+    /// ```no_run
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let plan = bridge.get_datastore().unwrap().cascading_delete_plan("1");
+    /// print!("{}", plan);
+    /// bridge.execute_delete_plan(&plan).unwrap();
+    /// ```
+    pub fn cascading_delete_plan(&self, resourcelink_id: impl AsRef<str>) -> DeletePlan {
+        let root = Link {
+            kind: LinkKind::Resourcelink,
+            id: resourcelink_id.as_ref().to_owned(),
+        };
+        let mut owned: Vec<Link> = Vec::new();
+        let mut stack = vec![root.clone()];
+        while let Some(link) = stack.pop() {
+            if owned.contains(&link) || !self.contains(link.kind.clone(), &link.id) {
+                continue;
+            }
+            if link.kind == LinkKind::Resourcelink {
+                stack.extend(self.references(&link).into_iter().rev());
+            }
+            owned.push(link);
+        }
+        owned.retain(|v| v.kind != LinkKind::Light && !(v.kind == LinkKind::Group && v.id == "0"));
+        let mut kept = Vec::new();
+        loop {
+            let outside: Vec<Link> = self
+                .dependent_candidates()
+                .filter(|v| !owned.contains(v))
+                .collect();
+            let shared: Vec<(Link, Vec<Link>)> = owned
+                .iter()
+                .filter(|v| **v != root)
+                .filter_map(|v| {
+                    let dependents: Vec<Link> = outside
+                        .iter()
+                        .filter(|k| self.references(k).contains(v))
+                        .cloned()
+                        .collect();
+                    if dependents.is_empty() {
+                        None
+                    } else {
+                        Some((v.clone(), dependents))
+                    }
+                })
+                .collect();
+            if shared.is_empty() {
+                break;
+            }
+            owned.retain(|v| !shared.iter().any(|(s, _)| s == v));
+            kept.extend(shared);
+        }
+        DeletePlan {
+            kept,
+            ..DeletePlan::ordered(owned)
+        }
+    }
+
+    /// Searches for resources that are left behind and can be deleted.
+    ///
+    /// The report contains scenes whose owner is not in the whitelist anymore, recyclable groups,
+    /// scenes, sensors and resourcelinks that are not referenced by any other resource, and rules
+    /// that were disabled by the bridge because a resource they use was deleted. Scenes that are
+    /// locked or referenced by another resource are never reported, regardless of their owner.
+    /// Nothing is deleted until the plan of the report is executed with
+    /// [`Bridge::execute_delete_plan`].
+    ///
+    /// [`Bridge::execute_delete_plan`]: ../../bridge/struct.Bridge.html#method.execute_delete_plan
+    ///
+    /// # Examples
+    ///
+    /// Print the report and delete the found resources.
+    /// ```no_run
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let report = bridge.get_datastore().unwrap().cleanup_report();
+    /// print!("{}", report);
+    /// bridge.execute_delete_plan(&report.delete_plan()).unwrap();
+    /// ```
+    pub fn cleanup_report(&self) -> CleanupReport {
+        let mut garbage = Vec::new();
+        let mut scenes: Vec<&Scene> = self.scenes.iter().collect();
+        scenes.sort_by_key(|v| v.last_update);
+        for scene in scenes {
+            if scene.locked || self.is_referenced(LinkKind::Scene, scene.id.as_str()) {
+                continue;
+            }
+            let reason = match &scene.owner {
+                Some(v) if !self.config.whitelist.iter().any(|u| &u.id == v) => {
+                    GarbageReason::OwnerDeleted(v.clone())
+                }
+                _ if scene.recycle => GarbageReason::Unreferenced,
+                _ => continue,
+            };
+            garbage.push(Garbage {
+                link: Link::from(scene.id.clone()),
+                name: scene.name.clone(),
+                last_update: scene.last_update,
+                reason,
+            });
+        }
+        let recyclables = self
+            .groups
+            .iter()
+            .filter(|v| v.recycle == Some(true))
+            .map(|v| (Link::from(v.id.clone()), &v.name))
+            .chain(
+                self.sensors
+                    .iter()
+                    .filter(|v| v.recycle == Some(true))
+                    .map(|v| (Link::from(v.id.clone()), &v.name)),
+            )
+            .chain(
+                self.resourcelinks
+                    .iter()
+                    .filter(|v| v.recycle)
+                    .map(|v| (Link::from(v.id.clone()), &v.name)),
+            );
+        for (link, name) in recyclables {
+            if !self.is_referenced(link.kind.clone(), &link.id) {
+                garbage.push(Garbage {
+                    link,
+                    name: name.clone(),
+                    last_update: None,
+                    reason: GarbageReason::Unreferenced,
+                });
+            }
+        }
+        for rule in &self.rules {
+            if rule.status == rule::Status::ResourceDeleted {
+                garbage.push(Garbage {
+                    link: Link::from(rule.id.clone()),
+                    name: rule.name.clone(),
+                    last_update: None,
+                    reason: GarbageReason::ResourceDeleted,
+                });
+            }
+        }
+        CleanupReport { garbage }
+    }
+
+    /// Returns whether a resource is referenced by any other resource.
+    fn is_referenced(&self, kind: LinkKind, id: &str) -> bool {
+        let in_group = match kind {
+            LinkKind::Group => self
+                .scenes
+                .iter()
+                .any(|v| v.group.as_ref().is_some_and(|g| g == id)),
+            LinkKind::Sensor => self
+                .groups
+                .iter()
+                .any(|v| v.sensors.iter().any(|s| s == id)),
+            _ => false,
+        };
+        in_group || !self.dependents(kind, id).is_empty()
+    }
+
+    fn dependent_candidates(&self) -> impl Iterator<Item = Link> + '_ {
+        let resourcelinks = self.resourcelinks.iter().map(|v| Link::from(v.id.clone()));
+        let rules = self.rules.iter().map(|v| Link::from(v.id.clone()));
+        let schedules = self.schedules.iter().map(|v| Link::from(v.id.clone()));
+        resourcelinks.chain(rules).chain(schedules)
+    }
+}
+
+/// An ordered list of resources that will be deleted.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeletePlan {
+    /// References to the resources in the order they will be deleted.
+    pub resources: Vec<Link>,
+    /// Resources that are not deleted, because resources outside of the plan depend on them.
+    ///
+    /// Each resource is paired with the resources that depend on it.
+    pub kept: Vec<(Link, Vec<Link>)>,
+}
+
+impl DeletePlan {
+    /// Creates a plan that deletes referencing resources before the resources they reference.
+    fn ordered(mut resources: Vec<Link>) -> Self {
+        resources.sort_by_key(|v| match v.kind {
+            LinkKind::Resourcelink | LinkKind::Unknown(_) => 0,
+            LinkKind::Rule => 1,
+            LinkKind::Schedule => 2,
+            LinkKind::Scene => 3,
+            LinkKind::Sensor => 4,
+            LinkKind::Group => 5,
+            LinkKind::Light => 6,
+        });
+        Self {
+            resources,
+            kept: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for DeletePlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for link in &self.resources {
+            writeln!(f, "Delete {}", link)?;
+        }
+        for (link, dependents) in &self.kept {
+            let dependents: Vec<String> = dependents.iter().map(ToString::to_string).collect();
+            writeln!(f, "Keep {} (used by {})", link, dependents.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Resources that can be deleted from a bridge.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CleanupReport {
+    /// Resources that were found by the analyzer.
+    pub garbage: Vec<Garbage>,
+}
+
+impl CleanupReport {
+    /// Whether no resources were found.
+    pub fn is_empty(&self) -> bool {
+        self.garbage.is_empty()
+    }
+
+    /// Returns a plan to delete all resources of the report.
+    pub fn delete_plan(&self) -> DeletePlan {
+        DeletePlan::ordered(self.garbage.iter().map(|v| v.link.clone()).collect())
+    }
+}
+
+impl fmt::Display for CleanupReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for garbage in &self.garbage {
+            writeln!(f, "{}", garbage)?;
+        }
+        Ok(())
+    }
+}
+
+/// A resource that is not used anymore.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Garbage {
+    /// Reference to the resource.
+    pub link: Link,
+    /// Name of the resource.
+    pub name: String,
+    /// Time the resource has been created or updated.
+    ///
+    /// Only available for scenes.
+    pub last_update: Option<chrono::NaiveDateTime>,
+    /// Why the resource is not used anymore.
+    pub reason: GarbageReason,
+}
+
+impl fmt::Display for Garbage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} '{}': {}", self.link, self.name, self.reason)?;
+        if let Some(v) = self.last_update {
+            write!(f, " (last updated {})", v)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reason why a resource is not used anymore.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GarbageReason {
+    /// The whitelist user that owns the resource does not exist anymore.
+    OwnerDeleted(String),
+    /// The resource is recyclable and not referenced by any other resource.
+    Unreferenced,
+    /// The rule references a resource that was deleted.
+    ResourceDeleted,
+}
+
+impl fmt::Display for GarbageReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OwnerDeleted(v) => write!(f, "owner '{}' does not exist anymore", v),
+            Self::Unreferenced => f.write_str("recyclable and not referenced"),
+            Self::ResourceDeleted => f.write_str("references a deleted resource"),
+        }
+    }
+}
+
+/// Splits an address into its parts, skipping the API path and username if present.
+fn address_parts(address: &str) -> impl Iterator<Item = &str> {
+    let mut parts = address.trim_start_matches('/').split('/').peekable();
+    if parts.peek() == Some(&"api") {
+        parts.nth(1);
+    }
+    parts
+}
+
+/// Parses the resource an address refers to.
+fn parse_address(address: &str) -> Option<Link> {
+    let mut parts = address_parts(address);
+    let kind = LinkKind::from_str(parts.next()?)?;
+    let id = parts.next()?;
+    Some(Link {
+        kind,
+        id: id.to_owned(),
+    })
+}
+
+/// Returns the resources referenced by the address and body of an action.
+fn action_references(action: &Action) -> Vec<Link> {
+    let mut references: Vec<Link> = parse_address(&action.address).into_iter().collect();
+    if let Some(v) = action.body.get("scene").and_then(|v| v.as_str()) {
+        references.push(Link {
+            kind: LinkKind::Scene,
+            id: v.to_owned(),
+        });
+    }
+    references
+}
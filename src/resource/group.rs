@@ -1,19 +1,33 @@
-use crate::resource::{self, Alert, Effect, ModifierType};
-use crate::Color;
+use crate::resource::{self, Alert, Effect, ModifierType, TransitionTimeError};
+use crate::{color, Color};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// A group of lights.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+///
+/// # Examples
+///
+/// Groups and all other resources can be serialized and deserialized again.
+/// ```
+/// use huelib::resource::Group;
+///
+/// let json = r#"{"name": "Kitchen", "lights": ["1", "2"], "sensors": [], "type": "Room",
+///     "class": "Kitchen", "state": {"all_on": false, "any_on": true}, "recycle": false}"#;
+/// let group: Group = serde_json::from_str(json).unwrap();
+/// let serialized = serde_json::to_string(&group).unwrap();
+/// assert_eq!(serde_json::from_str::<Group>(&serialized).unwrap(), group);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Group {
     /// Identifier of the group.
     #[serde(skip)]
-    pub id: String,
+    pub id: resource::GroupId,
     /// Name of the group.
     pub name: String,
     /// Identifiers of lights that are in this group.
-    pub lights: Vec<String>,
+    pub lights: Vec<resource::LightId>,
     /// Identifiers of sensors that are in this group.
-    pub sensors: Vec<String>,
+    pub sensors: Vec<resource::SensorId>,
     /// Kind of the group.
     #[serde(rename = "type")]
     pub kind: Kind,
@@ -35,7 +49,6 @@ pub struct Group {
     ///
     /// In AA:BB:CC:DD format for luminaire groups or AA:BB:CC:DD-XX format for
     /// lightsource groups, where XX is the lightsource position.
-    #[serde(rename = "unique_id")]
     pub unique_id: Option<String>,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: Option<bool>,
@@ -44,20 +57,22 @@ pub struct Group {
 impl resource::Resource for Group {}
 
 impl Group {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::GroupId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// Kind of a group.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Kind {
     /// Kind of a group that can be manually created.
     Creatable(CreatableKind),
     /// Kind of a group that is automatically created by the bridge and cannot be manually created.
     Immutable(ImmutableKind),
+    /// Value that is unknown to this library.
+    Unknown(String),
 }
 
 /// Kind of a group that can be manually created.
@@ -87,7 +102,7 @@ pub enum CreatableKind {
 }
 
 /// Kind of a group that is automatically created by the bridge and cannot be manually created.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum ImmutableKind {
     /// A special group containing all lights in the system.
     ///
@@ -108,7 +123,7 @@ pub enum ImmutableKind {
 
 /// Class of a group.
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Class {
     Attic,
     Balcony,
@@ -157,10 +172,14 @@ pub enum Class {
     #[serde(rename = "Top floor")]
     TopFloor,
     Upstairs,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// State of a group.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "group::State")]
 pub struct State {
     /// Whether any light in a group is on.
     pub any_on: bool,
@@ -174,9 +193,9 @@ pub struct Creator {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    lights: Option<Vec<String>>,
+    lights: Option<Vec<resource::LightId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    sensors: Option<Vec<String>>,
+    sensors: Option<Vec<resource::SensorId>>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     kind: Option<CreatableKind>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -189,7 +208,7 @@ impl resource::Creator for Creator {}
 
 impl Creator {
     /// Creates a new group creator.
-    pub fn new(name: impl Into<String>, lights: Vec<impl Into<String>>) -> Self {
+    pub fn new(name: impl Into<String>, lights: Vec<impl Into<resource::LightId>>) -> Self {
         Self {
             name: Some(name.into()),
             lights: Some(lights.into_iter().map(|v| v.into()).collect()),
@@ -198,7 +217,7 @@ impl Creator {
     }
 
     /// Sets the identifiers of the sensors of the group.
-    pub fn sensors(mut self, value: Vec<impl Into<String>>) -> Self {
+    pub fn sensors(mut self, value: Vec<impl Into<resource::SensorId>>) -> Self {
         self.sensors = Some(value.into_iter().map(|v| v.into()).collect());
         self
     }
@@ -228,9 +247,9 @@ pub struct AttributeModifier {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    lights: Option<Vec<String>>,
+    lights: Option<Vec<resource::LightId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    sensors: Option<Vec<String>>,
+    sensors: Option<Vec<resource::SensorId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     class: Option<Class>,
 }
@@ -245,13 +264,13 @@ impl AttributeModifier {
     }
 
     /// Sets the identifiers of the lights of the group.
-    pub fn lights(mut self, value: Vec<impl Into<String>>) -> Self {
+    pub fn lights(mut self, value: Vec<impl Into<resource::LightId>>) -> Self {
         self.lights = Some(value.into_iter().map(|v| v.into()).collect());
         self
     }
 
     /// Sets the identifiers of the sensors of the group.
-    pub fn sensors(mut self, value: Vec<impl Into<String>>) -> Self {
+    pub fn sensors(mut self, value: Vec<impl Into<resource::SensorId>>) -> Self {
         self.sensors = Some(value.into_iter().map(|v| v.into()).collect());
         self
     }
@@ -295,7 +314,7 @@ pub struct StateModifier {
     #[serde(skip_serializing_if = "Option::is_none", rename = "ct_inc")]
     color_temperature_increment: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    scene: Option<String>,
+    scene: Option<resource::SceneId>,
 }
 
 impl resource::Modifier for StateModifier {}
@@ -308,7 +327,12 @@ impl StateModifier {
     }
 
     /// Sets the brightness of the lights.
-    pub fn brightness(mut self, modifier_type: ModifierType, value: u8) -> Self {
+    ///
+    /// The value can be a raw value or a [`Brightness`].
+    ///
+    /// [`Brightness`]: ../../brightness/struct.Brightness.html
+    pub fn brightness(mut self, modifier_type: ModifierType, value: impl Into<u8>) -> Self {
+        let value = value.into();
         match modifier_type {
             ModifierType::Override => self.brightness = Some(value),
             ModifierType::Increment => self.brightness_increment = Some(value as i16),
@@ -356,6 +380,11 @@ impl StateModifier {
         self
     }
 
+    /// Sets the color temperature of the lights in kelvin.
+    pub fn color_temperature_kelvin(self, value: u16) -> Self {
+        self.color_temperature(ModifierType::Override, color::kelvin_to_mired(value))
+    }
+
     /// Sets the alert effect of the lights.
     pub fn alert(mut self, value: Alert) -> Self {
         self.alert = Some(value);
@@ -376,9 +405,57 @@ impl StateModifier {
         self
     }
 
+    /// Sets the transition time of the lights from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100 milliseconds. Returns an error if
+    /// the duration exceeds the [maximal transition time].
+    ///
+    /// [maximal transition time]: ../constant.MAX_TRANSITION_TIME.html
+    pub fn transition_duration(self, value: Duration) -> Result<Self, TransitionTimeError> {
+        Ok(self.transition_time(resource::transition_time(value)?))
+    }
+
     /// Sets the scene identifier of the group.
-    pub fn scene(mut self, value: impl Into<String>) -> Self {
+    pub fn scene(mut self, value: impl Into<resource::SceneId>) -> Self {
         self.scene = Some(value.into());
         self
     }
+
+    /// Checks the values of the modifier before it is sent to a group.
+    ///
+    /// The values are checked against the valid ranges of the API. Attributes that conflict
+    /// with each other, like an override and an increment of the same attribute or two
+    /// different color modes, are reported as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resource::{group, Modifier, ModifierType, StateValidationError};
+    ///
+    /// let modifier = group::StateModifier::new()
+    ///     .brightness(ModifierType::Override, 255)
+    ///     .brightness(ModifierType::Increment, 10);
+    /// let errors = modifier.validate().unwrap_err();
+    /// assert!(errors.contains(&StateValidationError::Brightness(255)));
+    /// assert!(errors.contains(&StateValidationError::Conflict("bri", "bri_inc")));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<resource::StateValidationError>> {
+        self.attributes()
+            .validate(Some(resource::COLOR_TEMPERATURE_RANGE))
+    }
+
+    fn attributes(&self) -> resource::StateAttributes {
+        resource::StateAttributes {
+            brightness: self.brightness,
+            hue: self.hue,
+            saturation: self.saturation,
+            color_space_coordinates: self.color_space_coordinates,
+            color_temperature: self.color_temperature,
+            brightness_increment: self.brightness_increment,
+            hue_increment: self.hue_increment,
+            saturation_increment: self.saturation_increment,
+            color_space_coordinates_increment: self.color_space_coordinates_increment,
+            color_temperature_increment: self.color_temperature_increment,
+        }
+    }
 }
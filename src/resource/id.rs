@@ -0,0 +1,172 @@
+use crate::resource::resourcelink::{Link, LinkKind};
+use serde::{Deserialize, Serialize};
+use std::{borrow::Borrow, convert::Infallible, fmt, str::FromStr};
+
+macro_rules! impl_id {
+    ($(#[$meta:meta])* $name:ident, $kind:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Creates a new identifier.
+            pub fn new(value: impl Into<String>) -> Self {
+                Self(value.into())
+            }
+
+            /// Returns the identifier as string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Converts the identifier into a string.
+            pub fn into_string(self) -> String {
+                self.0
+            }
+
+            /// Returns the identifier of a link if it references this kind of resource.
+            pub fn from_link(link: &Link) -> Option<Self> {
+                match link.kind {
+                    LinkKind::$kind => Some(Self(link.id.clone())),
+                    _ => None,
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self(s.to_owned()))
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(value.to_owned())
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(value: &String) -> Self {
+                Self(value.clone())
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(value: &$name) -> Self {
+                value.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl From<$name> for Link {
+            fn from(value: $name) -> Self {
+                Self {
+                    kind: LinkKind::$kind,
+                    id: value.0,
+                }
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    };
+}
+
+impl_id!(
+    /// Identifier of a light.
+    ///
+    /// Identifiers of different resources are distinct types, so that the identifier of a group
+    /// cannot be passed where the identifier of a light is expected. Identifiers can be created
+    /// from strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resource::LightId;
+    ///
+    /// let id = LightId::from("1");
+    /// assert_eq!(id, "1");
+    /// assert_eq!(serde_json::to_string(&id).unwrap(), "\"1\"");
+    /// ```
+    LightId,
+    Light
+);
+impl_id!(
+    /// Identifier of a group.
+    GroupId,
+    Group
+);
+impl_id!(
+    /// Identifier of a scene.
+    SceneId,
+    Scene
+);
+impl_id!(
+    /// Identifier of a sensor.
+    SensorId,
+    Sensor
+);
+impl_id!(
+    /// Identifier of a rule.
+    RuleId,
+    Rule
+);
+impl_id!(
+    /// Identifier of a schedule.
+    ScheduleId,
+    Schedule
+);
+impl_id!(
+    /// Identifier of a resourcelink.
+    ResourcelinkId,
+    Resourcelink
+);
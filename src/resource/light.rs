@@ -1,13 +1,14 @@
-use crate::resource::{self, Alert, ColorMode, Effect, ModifierType};
-use crate::Color;
+use crate::color::{self, Color, Gamut};
+use crate::resource::{self, Alert, ColorMode, Effect, ModifierType, TransitionTimeError};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// A light.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Light {
     /// Identifier of the light.
     #[serde(skip)]
-    pub id: String,
+    pub id: resource::LightId,
     /// Name of the light.
     pub name: String,
     /// Type of the light.
@@ -45,14 +46,15 @@ pub struct Light {
 impl resource::Resource for Light {}
 
 impl Light {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::LightId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// State of a light.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "light::State")]
 pub struct State {
     /// Whether the light is on.
     pub on: Option<bool>,
@@ -87,8 +89,38 @@ pub struct State {
     pub reachable: bool,
 }
 
+impl State {
+    /// Returns the color that the light currently shows.
+    ///
+    /// The color is determined by the color mode of the light. If the light does not report a
+    /// color mode, the first available attribute of xy, hue and saturation, and color
+    /// temperature is used.
+    pub fn color(&self) -> Option<Color> {
+        let from_xy = || {
+            self.color_space_coordinates
+                .map(|(x, y)| Color::from_space_coordinates(x, y))
+        };
+        let from_hs = || match (self.hue, self.saturation) {
+            (Some(hue), Some(saturation)) => Some(Color::from_hue_saturation(hue, saturation)),
+            _ => None,
+        };
+        let from_ct = || self.color_temperature.map(Color::from_color_temperature);
+        let color = match &self.color_mode {
+            Some(ColorMode::ColorSpaceCoordinates) => from_xy(),
+            Some(ColorMode::HueAndSaturation) => from_hs(),
+            Some(ColorMode::ColorTemperature) => from_ct(),
+            Some(ColorMode::Unknown(_)) | None => from_xy().or_else(from_hs).or_else(from_ct),
+        }?;
+        Some(Color {
+            brightness: self.brightness,
+            ..color
+        })
+    }
+}
+
 /// Information about software updates of a light.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "light::SoftwareUpdate")]
 pub struct SoftwareUpdate {
     /// State of software updates.
     pub state: SoftwareUpdateState,
@@ -98,7 +130,7 @@ pub struct SoftwareUpdate {
 }
 
 /// State of a software update.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SoftwareUpdateState {
     /// No updates are available.
@@ -106,10 +138,14 @@ pub enum SoftwareUpdateState {
     /// Device cannot be updated.
     NotUpdatable,
     // TODO: Add missing variants for states (https://github.com/yuqio/huelib-rs/issues/1)
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Configuration of a light.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "light::Config")]
 pub struct Config {
     /// Arche type of the light.
     #[serde(rename = "archetype")]
@@ -123,7 +159,7 @@ pub struct Config {
 }
 
 /// Startup configuration of a light.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct StartupConfig {
     /// Mode of the startup.
     pub mode: String,
@@ -132,7 +168,8 @@ pub struct StartupConfig {
 }
 
 /// Capabilities of a light.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "light::Capabilities")]
 pub struct Capabilities {
     /// Whether the light is certified.
     pub certified: bool,
@@ -143,7 +180,7 @@ pub struct Capabilities {
 }
 
 /// Control capabilities of a light.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct ControlCapabilities {
     /// Minimal dimlevel of the light.
     #[serde(rename = "mindimlevel")]
@@ -162,8 +199,21 @@ pub struct ControlCapabilities {
     pub color_temperature: Option<ColorTemperatureCapabilities>,
 }
 
+impl ControlCapabilities {
+    /// Returns the color gamut of the light.
+    ///
+    /// If the light does not report the corners of its gamut, the built-in gamut of its gamut
+    /// type is used.
+    pub fn gamut(&self) -> Option<Gamut> {
+        self.color_gamut
+            .as_deref()
+            .and_then(Gamut::from_points)
+            .or_else(|| self.color_gamut_type.as_deref().and_then(Gamut::from_type))
+    }
+}
+
 /// Color temperature capabilities of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct ColorTemperatureCapabilities {
     /// Minimal color temperature.
     pub min: usize,
@@ -171,8 +221,15 @@ pub struct ColorTemperatureCapabilities {
     pub max: usize,
 }
 
+impl ColorTemperatureCapabilities {
+    /// Limits a mired color temperature to the range that is supported by the light.
+    pub fn clamp(&self, mired: u16) -> u16 {
+        (mired as usize).clamp(self.min, self.max.max(self.min)) as u16
+    }
+}
+
 /// Streaming capabilities of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct StreamingCapabilities {
     /// Whether a renderer is enabled.
     pub renderer: bool,
@@ -198,7 +255,7 @@ impl AttributeModifier {
 }
 
 /// Modifier for the light state.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct StateModifier {
     #[serde(skip_serializing_if = "Option::is_none")]
     on: Option<bool>,
@@ -240,7 +297,12 @@ impl StateModifier {
     }
 
     /// Sets the brightness of the light.
-    pub fn brightness(mut self, modifier_type: ModifierType, value: u8) -> Self {
+    ///
+    /// The value can be a raw value or a [`Brightness`].
+    ///
+    /// [`Brightness`]: ../../brightness/struct.Brightness.html
+    pub fn brightness(mut self, modifier_type: ModifierType, value: impl Into<u8>) -> Self {
+        let value = value.into();
         match modifier_type {
             ModifierType::Override => self.brightness = Some(value),
             ModifierType::Increment => self.brightness_increment = Some(value as i16),
@@ -288,6 +350,39 @@ impl StateModifier {
         self
     }
 
+    /// Sets the color temperature of a light in kelvin.
+    pub fn color_temperature_kelvin(self, value: u16) -> Self {
+        self.color_temperature(ModifierType::Override, color::kelvin_to_mired(value))
+    }
+
+    /// Sets a white color temperature in kelvin that is supported by the light.
+    ///
+    /// If the light supports color temperatures, the temperature is limited to its range.
+    /// Otherwise, if the light supports colors, the temperature is approximated with a color in
+    /// the gamut of the light. Lights that support neither are not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib::resource::{light, Modifier};
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let light = bridge.get_light("1").unwrap();
+    /// let modifier = light::StateModifier::new().white(4000, &light.capabilities.control);
+    /// bridge.set_light_state("1", &modifier).unwrap();
+    /// ```
+    pub fn white(mut self, kelvin: u16, capabilities: &ControlCapabilities) -> Self {
+        if let Some(v) = capabilities.color_temperature {
+            let mired = v.clamp(color::kelvin_to_mired(kelvin));
+            self = self.color_temperature(ModifierType::Override, mired);
+        } else if let Some(v) = capabilities.gamut() {
+            self = self.color(Color::from_kelvin(kelvin).in_gamut(&v));
+        }
+        self
+    }
+
     /// Sets the alert effect of a light.
     pub fn alert(mut self, value: Alert) -> Self {
         self.alert = Some(value);
@@ -307,4 +402,74 @@ impl StateModifier {
         self.transition_time = Some(value);
         self
     }
+
+    /// Sets the transition time of the light from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100 milliseconds. Returns an error if
+    /// the duration exceeds the [maximal transition time].
+    ///
+    /// [maximal transition time]: ../constant.MAX_TRANSITION_TIME.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resource::{light, Modifier};
+    /// use std::time::Duration;
+    ///
+    /// let modifier = light::StateModifier::new()
+    ///     .transition_duration(Duration::from_millis(1250))
+    ///     .unwrap();
+    /// assert_eq!(modifier, light::StateModifier::new().transition_time(13));
+    /// assert!(light::StateModifier::new()
+    ///     .transition_duration(Duration::from_secs(7000))
+    ///     .is_err());
+    /// ```
+    pub fn transition_duration(self, value: Duration) -> Result<Self, TransitionTimeError> {
+        Ok(self.transition_time(resource::transition_time(value)?))
+    }
+
+    /// Checks the values of the modifier before it is sent to a light.
+    ///
+    /// The values are checked against the valid ranges of the API and the color temperature
+    /// range of the light. Attributes that conflict with each other, like an override and an
+    /// increment of the same attribute or two different color modes, are reported as well.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib::resource::{light, Modifier, ModifierType};
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let light = bridge.get_light("1").unwrap();
+    /// let modifier = light::StateModifier::new().color_temperature(ModifierType::Override, 600);
+    /// if let Err(errors) = modifier.validate(&light.capabilities.control) {
+    ///     errors.iter().for_each(|e| eprintln!("{}", e));
+    /// }
+    /// ```
+    pub fn validate(
+        &self,
+        capabilities: &ControlCapabilities,
+    ) -> Result<(), Vec<resource::StateValidationError>> {
+        let range = capabilities
+            .color_temperature
+            .map(|v| (v.min as u16, v.max as u16));
+        self.attributes().validate(range)
+    }
+
+    fn attributes(&self) -> resource::StateAttributes {
+        resource::StateAttributes {
+            brightness: self.brightness,
+            hue: self.hue,
+            saturation: self.saturation,
+            color_space_coordinates: self.color_space_coordinates,
+            color_temperature: self.color_temperature,
+            brightness_increment: self.brightness_increment,
+            hue_increment: self.hue_increment,
+            saturation_increment: self.saturation_increment,
+            color_space_coordinates_increment: self.color_space_coordinates_increment,
+            color_temperature_increment: self.color_temperature_increment,
+        }
+    }
 }
@@ -6,6 +6,8 @@ pub mod capabilities;
 ///
 /// [Configuration API]: https://developers.meethue.com/develop/hue-api/7-configuration-api
 pub mod config;
+/// Full state of a bridge.
+pub mod datastore;
 /// Bindings to the [Groups API].
 ///
 /// [Groups API]: https://developers.meethue.com/develop/hue-api/groupds-api
@@ -35,9 +37,13 @@ pub mod schedule;
 /// [Sensors API]: https://developers.meethue.com/develop/hue-api/5-sensors-api
 pub mod sensor;
 
+mod id;
+
 pub use capabilities::Capabilities;
 pub use config::Config;
+pub use datastore::Datastore;
 pub use group::Group;
+pub use id::{GroupId, LightId, ResourcelinkId, RuleId, ScheduleId, SceneId, SensorId};
 pub use light::Light;
 pub use resourcelink::Resourcelink;
 pub use rule::Rule;
@@ -45,12 +51,24 @@ pub use scene::Scene;
 pub use schedule::Schedule;
 pub use sensor::Sensor;
 
-use serde::{de, de::Error as DeError, Deserialize, Serialize};
+use serde::{de, de::Error as DeError, ser, ser::SerializeMap, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, time::Duration};
+use thiserror::Error as ThisError;
 
 /// Alert effect of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+///
+/// # Examples
+///
+/// Values that are unknown to this library are deserialized as `Unknown`.
+/// ```
+/// use huelib::resource::Alert;
+///
+/// let alert: Alert = serde_json::from_str("\"breathe\"").unwrap();
+/// assert_eq!(alert, Alert::Unknown("breathe".to_owned()));
+/// assert_eq!(serde_json::to_string(&alert).unwrap(), "\"breathe\"");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Alert {
     /// Performs one breathe cycle.
@@ -59,20 +77,26 @@ pub enum Alert {
     LSelect,
     /// Disables any alert.
     None,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Dynamic effect of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Effect {
     /// Cycles through all hues with the current brightness and saturation.
     Colorloop,
     /// Disables any effect.
     None,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Color mode of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum ColorMode {
     /// Uses a color temperatue to set the color of a light.
     #[serde(rename = "ct")]
@@ -83,6 +107,9 @@ pub enum ColorMode {
     /// Uses x and y coordinates in the color space to set the color of a light.
     #[serde(rename = "xy")]
     ColorSpaceCoordinates,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Action of a schedule or rule.
@@ -97,14 +124,27 @@ pub struct Action {
     pub body: HashMap<String, JsonValue>,
 }
 
+impl Action {
+    /// Returns the transition time that is set in the body of the action.
+    pub fn transition(&self) -> Option<Duration> {
+        self.body
+            .get("transitiontime")
+            .and_then(JsonValue::as_u64)
+            .map(|v| transition_duration(v.min(u16::MAX as u64) as u16))
+    }
+}
+
 /// Request type of an action.
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ActionRequestType {
     Put,
     Post,
     Delete,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Struct for new resources that were scanned by the bridge.
@@ -173,6 +213,17 @@ impl<'de> Deserialize<'de> for Scan {
     }
 }
 
+impl Serialize for Scan {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.resources.len() + 1))?;
+        map.serialize_entry("lastscan", &self.last_scan)?;
+        for resource in &self.resources {
+            map.serialize_entry(&resource.id, &resource.name)?;
+        }
+        map.end()
+    }
+}
+
 /// Status of the last scan for a new resource type.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LastScan {
@@ -198,6 +249,18 @@ impl<'de> Deserialize<'de> for LastScan {
     }
 }
 
+impl Serialize for LastScan {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            LastScan::DateTime(v) => {
+                serializer.serialize_str(&v.format("%Y-%m-%dT%H:%M:%S").to_string())
+            }
+            LastScan::Active => serializer.serialize_str("active"),
+            LastScan::None => serializer.serialize_str("none"),
+        }
+    }
+}
+
 /// Information about a resource that is returned from a scan.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ScanResource {
@@ -218,6 +281,188 @@ pub enum ModifierType {
     Decrement,
 }
 
+/// Maximal transition time that is accepted by the API.
+pub const MAX_TRANSITION_TIME: Duration = Duration::from_millis(u16::MAX as u64 * 100);
+
+/// Error that occurs when a transition time exceeds the [maximal transition time].
+///
+/// [maximal transition time]: constant.MAX_TRANSITION_TIME.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ThisError)]
+#[error("Transition time of {0:?} exceeds the maximum of 6553.5 seconds")]
+pub struct TransitionTimeError(pub Duration);
+
+/// Converts a duration to a transition time in multiples of 100 milliseconds.
+///
+/// The duration is rounded to the nearest multiple of 100 milliseconds.
+pub(crate) fn transition_time(value: Duration) -> Result<u16, TransitionTimeError> {
+    let steps = (value.as_millis() + 50) / 100;
+    if steps > u16::MAX as u128 {
+        return Err(TransitionTimeError(value));
+    }
+    Ok(steps as u16)
+}
+
+/// Converts a transition time in multiples of 100 milliseconds to a duration.
+pub(crate) fn transition_duration(value: u16) -> Duration {
+    Duration::from_millis(value as u64 * 100)
+}
+
+/// Color temperature range in mired that is accepted by the API.
+pub const COLOR_TEMPERATURE_RANGE: (u16, u16) = (153, 500);
+
+/// Problems that can be found while validating a state modifier.
+#[derive(Clone, Debug, PartialEq, ThisError)]
+pub enum StateValidationError {
+    /// The brightness is outside of the valid range.
+    #[error("Brightness {0} is not between 1 and 254")]
+    Brightness(u8),
+    /// The saturation is outside of the valid range.
+    #[error("Saturation {0} is not between 0 and 254")]
+    Saturation(u8),
+    /// The color space coordinates are outside of the valid range.
+    #[error("Color space coordinates ({0}, {1}) are not between 0 and 1")]
+    SpaceCoordinates(f32, f32),
+    /// The color temperature is outside of the range that is supported by the light.
+    #[error("Color temperature {value} is not between {min} and {max}")]
+    ColorTemperature {
+        /// Color temperature of the modifier.
+        value: u16,
+        /// Minimal supported color temperature.
+        min: u16,
+        /// Maximal supported color temperature.
+        max: u16,
+    },
+    /// The light does not support color temperatures.
+    #[error("Light does not support color temperatures")]
+    ColorTemperatureUnsupported,
+    /// An increment is outside of the valid range of its attribute.
+    #[error("Increment of '{0}' is out of range")]
+    Increment(&'static str),
+    /// Two attributes that cannot be set at the same time are set.
+    #[error("Attributes '{0}' and '{1}' cannot be set at the same time")]
+    Conflict(&'static str, &'static str),
+}
+
+/// Attributes of a light or group state modifier that are validated.
+pub(crate) struct StateAttributes {
+    pub(crate) brightness: Option<u8>,
+    pub(crate) hue: Option<u16>,
+    pub(crate) saturation: Option<u8>,
+    pub(crate) color_space_coordinates: Option<(f32, f32)>,
+    pub(crate) color_temperature: Option<u16>,
+    pub(crate) brightness_increment: Option<i16>,
+    pub(crate) hue_increment: Option<i32>,
+    pub(crate) saturation_increment: Option<i16>,
+    pub(crate) color_space_coordinates_increment: Option<(f32, f32)>,
+    pub(crate) color_temperature_increment: Option<i32>,
+}
+
+impl StateAttributes {
+    /// Validates the attributes against a supported color temperature range.
+    ///
+    /// If no range is given, setting the color temperature is an error.
+    pub(crate) fn validate(
+        &self,
+        color_temperature_range: Option<(u16, u16)>,
+    ) -> Result<(), Vec<StateValidationError>> {
+        let mut errors = Vec::new();
+        if let Some(v) = self.brightness {
+            if !(1..=254).contains(&v) {
+                errors.push(StateValidationError::Brightness(v));
+            }
+        }
+        if let Some(v) = self.saturation {
+            if v > 254 {
+                errors.push(StateValidationError::Saturation(v));
+            }
+        }
+        if let Some((x, y)) = self.color_space_coordinates {
+            if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+                errors.push(StateValidationError::SpaceCoordinates(x, y));
+            }
+        }
+        if let Some(value) = self.color_temperature {
+            match color_temperature_range {
+                Some((min, max)) if value < min || value > max => {
+                    errors.push(StateValidationError::ColorTemperature { value, min, max })
+                }
+                Some(_) => {}
+                None => errors.push(StateValidationError::ColorTemperatureUnsupported),
+            }
+        }
+        let increments = [
+            ("bri_inc", self.brightness_increment.map(i32::from), 254),
+            ("sat_inc", self.saturation_increment.map(i32::from), 254),
+            ("hue_inc", self.hue_increment, 65534),
+            ("ct_inc", self.color_temperature_increment, 65534),
+        ];
+        for (name, value, max) in increments.iter() {
+            if value.is_some_and(|v| v.abs() > *max) {
+                errors.push(StateValidationError::Increment(name));
+            }
+        }
+        if let Some((x, y)) = self.color_space_coordinates_increment {
+            if x.abs() > 0.5 || y.abs() > 0.5 {
+                errors.push(StateValidationError::Increment("xy_inc"));
+            }
+        }
+        let conflicts = [
+            (
+                "bri",
+                self.brightness.is_some(),
+                "bri_inc",
+                self.brightness_increment.is_some(),
+            ),
+            (
+                "hue",
+                self.hue.is_some(),
+                "hue_inc",
+                self.hue_increment.is_some(),
+            ),
+            (
+                "sat",
+                self.saturation.is_some(),
+                "sat_inc",
+                self.saturation_increment.is_some(),
+            ),
+            (
+                "xy",
+                self.color_space_coordinates.is_some(),
+                "xy_inc",
+                self.color_space_coordinates_increment.is_some(),
+            ),
+            (
+                "ct",
+                self.color_temperature.is_some(),
+                "ct_inc",
+                self.color_temperature_increment.is_some(),
+            ),
+        ];
+        let xy = self.color_space_coordinates.is_some()
+            || self.color_space_coordinates_increment.is_some();
+        let ct = self.color_temperature.is_some() || self.color_temperature_increment.is_some();
+        let hue_sat = self.hue.is_some()
+            || self.hue_increment.is_some()
+            || self.saturation.is_some()
+            || self.saturation_increment.is_some();
+        let modes = [
+            ("xy", xy, "hue", hue_sat),
+            ("xy", xy, "ct", ct),
+            ("ct", ct, "hue", hue_sat),
+        ];
+        for (a, a_set, b, b_set) in conflicts.iter().chain(modes.iter()) {
+            if *a_set && *b_set {
+                errors.push(StateValidationError::Conflict(a, b));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
 /// Trait for resources.
 pub trait Resource {}
 
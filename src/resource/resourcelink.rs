@@ -1,13 +1,35 @@
 use crate::resource;
-use serde::{de, de::Error, Deserialize, Serialize};
+use serde::{de, de::Error, ser, Deserialize, Serialize};
 use std::fmt;
 
 /// A resourcelink to group resources in the bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+///
+/// # Examples
+///
+/// Links to resources that are unknown to this library are kept with their raw kind.
+/// ```
+/// use huelib::resource::{resourcelink::Link, resourcelink::LinkKind, GroupId, Resourcelink};
+///
+/// let resourcelink: Resourcelink = serde_json::from_str(r#"{
+///     "name": "Routine",
+///     "description": "",
+///     "owner": "username",
+///     "type": "Link",
+///     "classid": 1,
+///     "recycle": false,
+///     "links": ["/groups/1", "/behavior_instance/2"]
+/// }"#).unwrap();
+/// assert_eq!(resourcelink.links[0], Link::from(GroupId::from("1")));
+/// assert_eq!(resourcelink.links[1].kind, LinkKind::Unknown("behavior_instance".into()));
+///
+/// let value = serde_json::to_value(&resourcelink).unwrap();
+/// assert_eq!(value["links"][1], "/behavior_instance/2");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Resourcelink {
     /// Identifier of the resourcelink.
     #[serde(skip)]
-    pub id: String,
+    pub id: resource::ResourcelinkId,
     /// Name of the resourcelink.
     pub name: String,
     /// Description of the resourcelink.
@@ -29,21 +51,36 @@ pub struct Resourcelink {
 impl resource::Resource for Resourcelink {}
 
 impl Resourcelink {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::ResourcelinkId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// Kind of a resourcelink.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Kind {
     /// The only variant.
     Link,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// A reference to a resource.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// # Examples
+///
+/// Links can be created from and converted into typed identifiers.
+/// ```
+/// use huelib::resource::{resourcelink::Link, GroupId, LightId};
+///
+/// let link = Link::from(LightId::from("1"));
+/// assert_eq!(link.to_string(), "/lights/1");
+/// assert_eq!(LightId::from_link(&link), Some(LightId::from("1")));
+/// assert_eq!(GroupId::from_link(&link), None);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Link {
     /// Kind of the resource.
     pub kind: LinkKind,
@@ -51,19 +88,29 @@ pub struct Link {
     pub id: String,
 }
 
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "/{}/{}", self.kind, self.id)
+    }
+}
+
+impl Serialize for Link {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl<'de> Deserialize<'de> for Link {
     fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let value: String = Deserialize::deserialize(deserializer)?;
-        let mut values: Vec<&str> = value.split('/').collect();
-        let id_str = values
-            .pop()
-            .ok_or_else(|| D::Error::custom("expected link in the format /<kind>/<id>"))?;
-        let kind_str = values
-            .pop()
+        let (path, id_str) = value
+            .rsplit_once('/')
             .ok_or_else(|| D::Error::custom("expected link in the format /<kind>/<id>"))?;
+        let kind_str = path.rsplit('/').next().unwrap_or_default();
+        let path = path.strip_prefix('/').unwrap_or(path);
         Ok(Self {
             kind: LinkKind::from_str(kind_str)
-                .ok_or_else(|| D::Error::custom(format!("invalid link type '{}'", kind_str)))?,
+                .unwrap_or_else(|| LinkKind::Unknown(path.to_owned())),
             id: id_str.to_owned(),
         })
     }
@@ -71,7 +118,7 @@ impl<'de> Deserialize<'de> for Link {
 
 /// Kind of a link.
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum LinkKind {
     Group,
     Light,
@@ -80,10 +127,12 @@ pub enum LinkKind {
     Scene,
     Schedule,
     Sensor,
+    /// Path of a resource that is unknown to this library, for example `behavior_instance`.
+    Unknown(String),
 }
 
 impl LinkKind {
-    fn from_str(value: &str) -> Option<Self> {
+    pub(crate) fn from_str(value: &str) -> Option<Self> {
         match value {
             "groups" => Some(Self::Group),
             "lights" => Some(Self::Light),
@@ -110,6 +159,7 @@ impl fmt::Display for LinkKind {
                 Self::Scene => "scenes",
                 Self::Schedule => "schedules",
                 Self::Sensor => "sensors",
+                Self::Unknown(v) => v,
             }
         )
     }
@@ -124,7 +174,7 @@ pub struct Creator {
     description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     owner: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     kind: Option<Kind>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "classid")]
     class_id: Option<u16>,
@@ -187,7 +237,7 @@ pub struct Modifier {
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     kind: Option<Kind>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "classid")]
     class_id: Option<u16>,
@@ -1,22 +1,33 @@
-use crate::resource::{self, Action};
+use crate::resource::{self, Action, Capabilities, Datastore};
 use crate::util;
 use serde::{Deserialize, Serialize};
+use thiserror::Error as ThisError;
+
+/// Maximum number of conditions of a single rule.
+pub const MAX_CONDITIONS: usize = 8;
+
+/// Maximum number of actions of a single rule.
+pub const MAX_ACTIONS: usize = 8;
 
 /// A rule for resources on a bridge.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Rule {
     /// Identifier of the rule.
     #[serde(skip)]
-    pub id: String,
+    pub id: resource::RuleId,
     /// Name of the rule.
     pub name: String,
     /// Owner of the rule.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    #[serde(
+        deserialize_with = "util::deserialize_option_string",
+        serialize_with = "util::serialize_option_string"
+    )]
     pub owner: Option<String>,
     /// When the rule was last triggered.
+    #[serde(rename = "lasttriggered")]
     #[serde(
-        rename = "lasttriggered",
-        deserialize_with = "util::deserialize_option_date_time"
+        deserialize_with = "util::deserialize_option_date_time",
+        serialize_with = "util::serialize_option_date_time"
     )]
     pub last_triggered: Option<chrono::NaiveDateTime>,
     /// How often the rule was triggered.
@@ -35,14 +46,14 @@ pub struct Rule {
 impl resource::Resource for Rule {}
 
 impl Rule {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::RuleId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// Status of a rule.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Status {
     /// The rule is enabled.
@@ -51,6 +62,9 @@ pub enum Status {
     Disabled,
     /// The rule was deleted.
     ResourceDeleted,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Condition of a rule.
@@ -97,6 +111,9 @@ pub enum ConditionOperator {
     /// Current time is not in given time interval.
     #[serde(rename = "not in")]
     NotIn,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Struct for creating a rule.
@@ -135,6 +152,135 @@ impl Creator {
         self.status = Some(value);
         self
     }
+
+    /// Checks the rule against the limits of the bridge.
+    ///
+    /// The number of conditions and actions is compared to [`MAX_CONDITIONS`], [`MAX_ACTIONS`]
+    /// and the remaining budget in the capabilities of the bridge. The address of every condition
+    /// and action must refer to a resource in the datastore. All problems that are found are
+    /// returned at once.
+    ///
+    /// [`MAX_CONDITIONS`]: constant.MAX_CONDITIONS.html
+    /// [`MAX_ACTIONS`]: constant.MAX_ACTIONS.html
+    ///
+    /// # Examples
+    ///
+    /// Validate a rule before creating it.
+    /// ```no_run
+    /// use huelib::resource::{rule, Action, ActionRequestType};
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username");
+    /// let condition = rule::Condition {
+    ///     address: "/sensors/2/state/buttonevent".into(),
+    ///     operator: rule::ConditionOperator::Equals,
+    ///     value: Some("1002".into()),
+    /// };
+    /// let action = Action {
+    ///     address: "/groups/0/action".into(),
+    ///     request_type: ActionRequestType::Put,
+    ///     body: serde_json::from_str(r#"{"on": true}"#).unwrap(),
+    /// };
+    /// let creator = rule::Creator::new(vec![condition], vec![action]);
+    ///
+    /// let datastore = bridge.get_datastore().unwrap();
+    /// let capabilities = bridge.get_capabilities().unwrap();
+    /// match creator.validate(&datastore, &capabilities) {
+    ///     Ok(()) => println!("Created rule with id '{}'", bridge.create_rule(&creator).unwrap()),
+    ///     Err(errors) => errors.iter().for_each(|e| eprintln!("{}", e)),
+    /// };
+    /// ```
+    pub fn validate(
+        &self,
+        datastore: &Datastore,
+        capabilities: &Capabilities,
+    ) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let conditions = self.conditions.as_deref().unwrap_or_default();
+        let actions = self.actions.as_deref().unwrap_or_default();
+        let info = &capabilities.rules;
+        if info.available == 0 {
+            errors.push(ValidationError::RuleEngineFull);
+        }
+        if conditions.is_empty() {
+            errors.push(ValidationError::NoConditions);
+        } else if conditions.len() > MAX_CONDITIONS {
+            errors.push(ValidationError::TooManyConditions(conditions.len()));
+        } else if conditions.len() > info.conditions.available {
+            errors.push(ValidationError::ConditionsUnavailable {
+                required: conditions.len(),
+                available: info.conditions.available,
+            });
+        }
+        if actions.is_empty() {
+            errors.push(ValidationError::NoActions);
+        } else if actions.len() > MAX_ACTIONS {
+            errors.push(ValidationError::TooManyActions(actions.len()));
+        } else if actions.len() > info.actions.available {
+            errors.push(ValidationError::ActionsUnavailable {
+                required: actions.len(),
+                available: info.actions.available,
+            });
+        }
+        for condition in conditions {
+            if !datastore.contains_address(&condition.address) {
+                errors.push(ValidationError::ConditionAddress(condition.address.clone()));
+            }
+        }
+        for action in actions {
+            if !datastore.contains_address(&action.address) {
+                errors.push(ValidationError::ActionAddress(action.address.clone()));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Problems that can be found while validating a rule creator.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ValidationError {
+    /// The bridge cannot store any more rules.
+    #[error("No more rules can be created on the bridge")]
+    RuleEngineFull,
+    /// The rule has no conditions.
+    #[error("Rule has no conditions")]
+    NoConditions,
+    /// The rule has no actions.
+    #[error("Rule has no actions")]
+    NoActions,
+    /// The rule has more conditions than allowed for a single rule.
+    #[error("Rule has {0} conditions, but at most {max} are allowed", max = MAX_CONDITIONS)]
+    TooManyConditions(usize),
+    /// The rule has more actions than allowed for a single rule.
+    #[error("Rule has {0} actions, but at most {max} are allowed", max = MAX_ACTIONS)]
+    TooManyActions(usize),
+    /// The bridge has not enough conditions left for the rule.
+    #[error("Rule has {required} conditions, but only {available} are available")]
+    ConditionsUnavailable {
+        /// Number of conditions of the rule.
+        required: usize,
+        /// Number of conditions that are available on the bridge.
+        available: usize,
+    },
+    /// The bridge has not enough actions left for the rule.
+    #[error("Rule has {required} actions, but only {available} are available")]
+    ActionsUnavailable {
+        /// Number of actions of the rule.
+        required: usize,
+        /// Number of actions that are available on the bridge.
+        available: usize,
+    },
+    /// The address of a condition does not refer to an existing resource.
+    #[error("Condition address '{0}' does not refer to an existing resource")]
+    ConditionAddress(String),
+    /// The address of an action does not refer to an existing resource.
+    #[error("Action address '{0}' does not refer to an existing resource")]
+    ActionAddress(String),
 }
 
 /// Struct for modifying a rule.
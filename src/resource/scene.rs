@@ -1,26 +1,29 @@
-use crate::resource::{self, Effect};
-use crate::{util, Color};
-use serde::{Deserialize, Serialize};
-use serde_repr::Deserialize_repr;
+use crate::resource::{self, Effect, TransitionTimeError};
+use crate::{color, util, Color};
+use serde::{de, ser, Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// A scene.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Scene {
     /// Identifier of the scene.
-    #[serde(skip_deserializing)]
-    pub id: String,
+    #[serde(skip)]
+    pub id: resource::SceneId,
     /// Name of the scene.
     pub name: String,
     /// Kind of the scene.
     #[serde(rename = "type")]
     pub kind: Kind,
     /// Identifier of the group that the scene is linked to.
-    pub group: Option<String>,
+    pub group: Option<resource::GroupId>,
     /// Identifier of the lights that are in this scene.
-    pub lights: Option<Vec<String>>,
+    pub lights: Option<Vec<resource::LightId>>,
     /// Whitelist user that created or modified the content of the scene.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    #[serde(
+        deserialize_with = "util::deserialize_option_string",
+        serialize_with = "util::serialize_option_string"
+    )]
     pub owner: Option<String>,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: bool,
@@ -43,24 +46,32 @@ pub struct Scene {
     pub last_update: Option<chrono::NaiveDateTime>,
     /// Version of the scene document.
     pub version: Version,
+    /// States of the lights in the scene.
+    ///
+    /// Only available with an individual scene resource.
+    #[serde(rename = "lightstates")]
+    pub light_states: Option<HashMap<resource::LightId, LightStateModifier>>,
 }
 
 impl resource::Resource for Scene {}
 
 impl Scene {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::SceneId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// Kind of a scene.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Kind {
     /// Represents a scene with lights.
     LightScene,
     /// Represents a scene which links to a specific group.
     GroupScene,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Version of a scene document.
@@ -75,13 +86,45 @@ pub struct AppData {
 }
 
 /// Version of a scene document.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize_repr)]
-#[repr(i32)]
+///
+/// # Examples
+///
+/// Unknown versions are serialized with their original value.
+/// ```
+/// use huelib::resource::scene::Version;
+///
+/// let version: Version = serde_json::from_str("3").unwrap();
+/// assert_eq!(version, Version::Unknown(3));
+/// assert_eq!(serde_json::to_string(&version).unwrap(), "3");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Version {
     /// Scene was created with a PUT request.
-    Put = 1,
+    Put,
     /// Scene was created with a POST request.
-    Post = 2,
+    Post,
+    /// Value that is unknown to this library.
+    Unknown(i32),
+}
+
+impl Serialize for Version {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(match self {
+            Self::Put => 1,
+            Self::Post => 2,
+            Self::Unknown(v) => *v,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match i32::deserialize(deserializer)? {
+            1 => Self::Put,
+            2 => Self::Post,
+            v => Self::Unknown(v),
+        })
+    }
 }
 
 /// Struct for creating a scene.
@@ -90,20 +133,24 @@ pub struct Creator {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    lights: Option<Vec<String>>,
+    lights: Option<Vec<resource::LightId>>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     kind: Option<Kind>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<resource::GroupId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recycle: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "appdata")]
     app_data: Option<AppData>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "lightstates")]
-    light_states: Option<HashMap<String, LightStateModifier>>,
+    light_states: Option<HashMap<resource::LightId, LightStateModifier>>,
 }
 
 impl resource::Creator for Creator {}
 
 impl Creator {
     /// Creates a new scene creator.
-    pub fn new(name: impl Into<String>, lights: Vec<impl Into<String>>) -> Self {
+    pub fn new(name: impl Into<String>, lights: Vec<impl Into<resource::LightId>>) -> Self {
         Self {
             name: Some(name.into()),
             lights: Some(lights.into_iter().map(|v| v.into()).collect()),
@@ -117,6 +164,22 @@ impl Creator {
         self
     }
 
+    /// Sets the identifier of the group that the scene is linked to.
+    ///
+    /// This is required if the kind of the scene is [`GroupScene`].
+    ///
+    /// [`GroupScene`]: enum.Kind.html#variant.GroupScene
+    pub fn group(mut self, value: impl Into<resource::GroupId>) -> Self {
+        self.group = Some(value.into());
+        self
+    }
+
+    /// Sets whether the scene is automatically deleted when not referenced anymore.
+    pub fn recycle(mut self, value: bool) -> Self {
+        self.recycle = Some(value);
+        self
+    }
+
     /// Sets the data of the app data.
     pub fn app_data(mut self, value: impl Into<String>) -> Self {
         self.app_data = Some(AppData {
@@ -136,7 +199,11 @@ impl Creator {
     }
 
     /// Sets the state of a light.
-    pub fn light_state(mut self, id: impl Into<String>, modifier: LightStateModifier) -> Self {
+    pub fn light_state(
+        mut self,
+        id: impl Into<resource::LightId>,
+        modifier: LightStateModifier,
+    ) -> Self {
         let mut light_states = self.light_states.unwrap_or_default();
         light_states.insert(id.into(), modifier);
         self.light_states = Some(light_states);
@@ -145,7 +212,7 @@ impl Creator {
 }
 
 /// Struct for modifying the state of a light.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct LightStateModifier {
     #[serde(skip_serializing_if = "Option::is_none")]
     on: Option<bool>,
@@ -175,8 +242,12 @@ impl LightStateModifier {
     }
 
     /// Sets the brightness of the lights.
-    pub fn brightness(mut self, value: u8) -> Self {
-        self.brightness = Some(value);
+    ///
+    /// The value can be a raw value or a [`Brightness`].
+    ///
+    /// [`Brightness`]: ../../brightness/struct.Brightness.html
+    pub fn brightness(mut self, value: impl Into<u8>) -> Self {
+        self.brightness = Some(value.into());
         self
     }
 
@@ -207,17 +278,39 @@ impl LightStateModifier {
         self
     }
 
+    /// Sets the color temperature of the lights in kelvin.
+    pub fn color_temperature_kelvin(self, value: u16) -> Self {
+        self.color_temperature(color::kelvin_to_mired(value))
+    }
+
     /// Sets the effect of the lights.
     pub fn effect(mut self, value: Effect) -> Self {
         self.effect = Some(value);
         self
     }
 
+    /// Returns the transition time of the light state.
+    ///
+    /// Only available for light states that are returned by the bridge.
+    pub fn transition(&self) -> Option<Duration> {
+        self.transition_time.map(resource::transition_duration)
+    }
+
     /// Sets the transition time of the lights.
     pub fn transition_time(mut self, value: u16) -> Self {
         self.transition_time = Some(value);
         self
     }
+
+    /// Sets the transition time of the lights from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100 milliseconds. Returns an error if
+    /// the duration exceeds the [maximal transition time].
+    ///
+    /// [maximal transition time]: ../constant.MAX_TRANSITION_TIME.html
+    pub fn transition_duration(self, value: Duration) -> Result<Self, TransitionTimeError> {
+        Ok(self.transition_time(resource::transition_time(value)?))
+    }
 }
 
 /// Struct for modifying a scene.
@@ -226,9 +319,9 @@ pub struct Modifier {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    lights: Option<Vec<String>>,
+    lights: Option<Vec<resource::LightId>>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "lightstates")]
-    light_states: Option<HashMap<String, LightStateModifier>>,
+    light_states: Option<HashMap<resource::LightId, LightStateModifier>>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "storelightstate")]
     store_light_state: Option<bool>,
 }
@@ -243,13 +336,17 @@ impl Modifier {
     }
 
     /// Sets the indentifiers of the lights that are in this scene.
-    pub fn lights(mut self, value: Vec<impl Into<String>>) -> Self {
+    pub fn lights(mut self, value: Vec<impl Into<resource::LightId>>) -> Self {
         self.lights = Some(value.into_iter().map(|v| v.into()).collect());
         self
     }
 
     /// Sets the state of a light.
-    pub fn light_state(mut self, id: impl Into<String>, modifier: LightStateModifier) -> Self {
+    pub fn light_state(
+        mut self,
+        id: impl Into<resource::LightId>,
+        modifier: LightStateModifier,
+    ) -> Self {
         let mut light_states = self.light_states.unwrap_or_default();
         light_states.insert(id.into(), modifier);
         self.light_states = Some(light_states);
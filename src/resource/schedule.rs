@@ -2,11 +2,11 @@ use crate::resource::{self, Action};
 use serde::{Deserialize, Serialize};
 
 /// Schedule of a resource.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Schedule {
     /// Identifier of the schedule.
     #[serde(skip)]
-    pub id: String,
+    pub id: resource::ScheduleId,
     /// Name of the schedule.
     pub name: String,
     /// Description of the schedule.
@@ -30,20 +30,23 @@ pub struct Schedule {
 impl resource::Resource for Schedule {}
 
 impl Schedule {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::ScheduleId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// Status of a schedule.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Status {
     /// The schedule is enabled.
     Enabled,
     /// The schedule is disabled.
     Disabled,
+    /// Value that is unknown to this library.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Struct for creating a schedule.
@@ -53,13 +56,13 @@ pub struct Creator {
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "command")]
     action: Option<Action>,
     #[serde(skip_serializing_if = "Option::is_none")]
     localtime: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     status: Option<Status>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "autodelete")]
     auto_delete: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     recycle: Option<bool>,
@@ -115,7 +118,7 @@ pub struct Modifier {
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "command")]
     action: Option<Action>,
     #[serde(skip_serializing_if = "Option::is_none")]
     localtime: Option<String>,
@@ -2,11 +2,11 @@ use crate::{resource, util};
 use serde::{Deserialize, Serialize};
 
 /// A sensor.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Sensor {
     /// Identifier of the sensor.
     #[serde(skip)]
-    pub id: String,
+    pub id: resource::SensorId,
     /// Name of the sensor.
     pub name: String,
     /// Type name of the sensor.
@@ -35,30 +35,33 @@ pub struct Sensor {
 impl resource::Resource for Sensor {}
 
 impl Sensor {
-    pub(crate) fn with_id(mut self, id: impl Into<String>) -> Self {
+    pub(crate) fn with_id(mut self, id: impl Into<resource::SensorId>) -> Self {
         self.id = id.into();
         self
     }
 }
 
 /// Current state of a sensor.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "sensor::State")]
 pub struct State {
     /// Whether the sensor is present.
     pub presence: Option<bool>,
     /// Flag of the sensor.
     pub flag: Option<bool>,
     /// The current battery state in percent.
+    #[serde(rename = "lastupdated")]
     #[serde(
-        rename = "lastupdated",
-        deserialize_with = "util::deserialize_option_date_time"
+        deserialize_with = "util::deserialize_option_date_time",
+        serialize_with = "util::serialize_option_date_time"
     )]
     pub last_updated: Option<chrono::NaiveDateTime>,
     // TODO: Add missing attributes (https://github.com/yuqio/huelib-rs/issues/2)
 }
 
 /// Configuration of a sensor.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "sensor::Config")]
 pub struct Config {
     /// Whether the sensor is on.
     pub on: bool,
@@ -1,7 +1,7 @@
-use serde::{de, Deserialize};
+use serde::{de, de::DeserializeOwned, Deserialize};
 use serde_json::Value as JsonValue;
 use serde_repr::Deserialize_repr;
-use std::fmt;
+use std::{convert::Infallible, fmt, str::FromStr};
 use thiserror::Error as ThisError;
 
 /// A response that is returned from the Philips Hue API.
@@ -38,6 +38,18 @@ impl<T: fmt::Display> fmt::Display for Response<T> {
 /// View the [API documentation] for more information.
 ///
 /// [API documentation]: https://developers.meethue.com/develop/hue-api/error-messages
+///
+/// # Examples
+///
+/// Error kinds that are unknown to this library are deserialized as `UnkownError`.
+/// ```
+/// use huelib::response::{Error, ErrorKind};
+///
+/// let error: Error = serde_json::from_str(
+///     r#"{"type": 1234, "address": "/lights/1", "description": "new error"}"#,
+/// ).unwrap();
+/// assert_eq!(error.kind, ErrorKind::UnkownError);
+/// ```
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, ThisError)]
 #[error("{description}")]
 pub struct Error {
@@ -93,14 +105,146 @@ pub enum ErrorKind {
     SourceFactoryNew = 802,
     InvalidState = 803,
     InternalError = 901,
+    /// Error kind that is unknown to this library.
+    #[serde(other)]
     UnkownError,
 }
 
+impl ErrorKind {
+    /// Returns the category of the error kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::response::{ErrorCategory, ErrorKind};
+    ///
+    /// assert_eq!(ErrorKind::GroupTableIsFull.category(), ErrorCategory::Full);
+    /// assert_eq!(ErrorKind::DeviceIsSetToOff.category(), ErrorCategory::DeviceOff);
+    /// ```
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            Self::UnauthorizedUser | Self::LinkButtonNotPressed => ErrorCategory::Unauthorized,
+            Self::ResourceNotAvailable => ErrorCategory::NotFound,
+            Self::TooManyItemsInList
+            | Self::CommissionableLightListIsFull
+            | Self::GroupTableIsFull
+            | Self::SceneCouldNotBeCreatedBufferIsFull
+            | Self::SensorListIsFull
+            | Self::CommissionableSensorListIsFull
+            | Self::RuleEngineFull
+            | Self::ScheduleListIsFull => ErrorCategory::Full,
+            Self::BodyContainsInvalidJson
+            | Self::MissingParametersInBody
+            | Self::ParameterNotAvailable
+            | Self::InvalidValueForParameter
+            | Self::ParameterIsNotModifiable
+            | Self::ScheduleTimezoneNotValid
+            | Self::ScheduleCannotSetTimeAndLocalTime
+            | Self::CannotEnableScheduleTimeInPast => ErrorCategory::InvalidParameter,
+            Self::DeviceIsSetToOff => ErrorCategory::DeviceOff,
+            _ => ErrorCategory::Other,
+        }
+    }
+}
+
+/// Category of errors from responses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ErrorCategory {
+    /// The user is not authorized or the link button was not pressed.
+    Unauthorized,
+    /// The resource does not exist.
+    NotFound,
+    /// A list or table of the bridge is full.
+    Full,
+    /// A parameter of the request is missing or invalid.
+    InvalidParameter,
+    /// The device is turned off.
+    DeviceOff,
+    /// Any other error.
+    Other,
+}
+
+/// Address of an attribute of a resource, for example `/lights/1/state/bri`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Address {
+    /// Kind of the resource, for example `lights`.
+    pub kind: String,
+    /// Identifier of the resource.
+    ///
+    /// This is `None` for resources without identifiers like `config`.
+    pub id: Option<String>,
+    /// Path of the attribute relative to the resource, for example `state/bri`.
+    pub attribute: String,
+}
+
+impl Address {
+    /// Returns the name of the attribute, which is the last part of the attribute path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::response::Address;
+    ///
+    /// let address: Address = "/lights/1/state/bri".parse().unwrap();
+    /// assert_eq!(address.kind, "lights");
+    /// assert_eq!(address.id.as_deref(), Some("1"));
+    /// assert_eq!(address.attribute, "state/bri");
+    /// assert_eq!(address.name(), "bri");
+    /// ```
+    pub fn name(&self) -> &str {
+        self.attribute.rsplit('/').next().unwrap_or_default()
+    }
+
+    /// Returns whether the address matches an attribute path or name.
+    fn matches(&self, attribute: &str) -> bool {
+        self.attribute == attribute || self.name() == attribute
+    }
+}
+
+impl FromStr for Address {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim_start_matches('/').splitn(2, '/');
+        let kind = parts.next().unwrap_or_default().to_owned();
+        let rest = parts.next().unwrap_or_default();
+        let (id, attribute) = match kind.as_str() {
+            "config" | "capabilities" => (None, rest),
+            _ => {
+                let mut parts = rest.splitn(2, '/');
+                let id = parts
+                    .next()
+                    .filter(|v| !v.is_empty())
+                    .map(ToOwned::to_owned);
+                (id, parts.next().unwrap_or_default())
+            }
+        };
+        Ok(Self {
+            kind,
+            id,
+            attribute: attribute.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "/{}", self.kind)?;
+        if let Some(id) = &self.id {
+            write!(f, "/{}", id)?;
+        }
+        if !self.attribute.is_empty() {
+            write!(f, "/{}", self.attribute)?;
+        }
+        Ok(())
+    }
+}
+
 /// A response type that is used when modifying a resource.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Modified {
     /// Address of the changed attribute.
-    pub address: String,
+    pub address: Address,
     /// New value of the attribute.
     pub value: JsonValue,
 }
@@ -125,8 +269,8 @@ impl<'de> de::Deserialize<'de> for Modified {
             fn visit_map<V: de::MapAccess<'de>>(self, mut map: V) -> Result<Modified, V::Error> {
                 let mut address = None;
                 let mut value = None;
-                while let Some(key) = map.next_key()? {
-                    address = Some(key);
+                while let Some(key) = map.next_key::<String>()? {
+                    address = Some(key.parse().map_err(de::Error::custom)?);
                     value = Some(map.next_value()?);
                 }
                 let address = address.ok_or_else(|| de::Error::missing_field("address"))?;
@@ -139,3 +283,114 @@ impl<'de> de::Deserialize<'de> for Modified {
         deserializer.deserialize_struct("Modified", FIELDS, ModifiedVisitor)
     }
 }
+
+/// Result of a request that modifies a resource.
+///
+/// The Philips Hue API returns a response for every modified attribute, where some attributes
+/// can be modified while others fail.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::response::Modification;
+///
+/// let modification: Modification = serde_json::from_str(r#"[
+///     {"success": {"/lights/1/state/bri": 200}},
+///     {"error": {"type": 201, "address": "/lights/1/state/hue", "description": "device is off"}}
+/// ]"#).unwrap();
+/// assert_eq!(modification.modified_value::<u8>("bri"), Some(200));
+/// assert!(modification.error("state/hue").is_some());
+/// assert!(modification.into_result().is_err());
+/// ```
+///
+/// Every error is kept if no attribute could be modified.
+/// ```
+/// use huelib::response::Modification;
+///
+/// let modification: Modification = serde_json::from_str(r#"[
+///     {"error": {"type": 201, "address": "/lights/1/state/bri", "description": "device is off"}},
+///     {"error": {"type": 201, "address": "/lights/1/state/hue", "description": "device is off"}}
+/// ]"#).unwrap();
+/// assert!(modification.succeeded.is_empty());
+/// assert_eq!(modification.failed.len(), 2);
+/// assert!(modification.error("bri").is_some());
+/// assert!(modification.error("hue").is_some());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(from = "Vec<Response<Modified>>")]
+pub struct Modification {
+    /// Attributes that were modified.
+    pub succeeded: Vec<Modified>,
+    /// Errors of attributes that could not be modified.
+    pub failed: Vec<Error>,
+}
+
+impl Modification {
+    /// Returns whether all attributes were modified.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Returns the new value of an attribute.
+    ///
+    /// The attribute can be either the path relative to the resource like `state/bri` or only
+    /// the name like `bri`. Returns `None` if the attribute was not modified or the value cannot
+    /// be converted to the given type.
+    pub fn modified_value<T: DeserializeOwned>(&self, attribute: &str) -> Option<T> {
+        self.succeeded
+            .iter()
+            .find(|v| v.address.matches(attribute))
+            .and_then(|v| serde_json::from_value(v.value.clone()).ok())
+    }
+
+    /// Returns the error of an attribute that could not be modified.
+    ///
+    /// The attribute is matched like in [`modified_value`].
+    ///
+    /// [`modified_value`]: #method.modified_value
+    pub fn error(&self, attribute: &str) -> Option<&Error> {
+        self.failed.iter().find(|v| {
+            v.address
+                .parse::<Address>()
+                .is_ok_and(|v| v.matches(attribute))
+        })
+    }
+
+    /// Converts the modification into the modified attributes, or the first error if any
+    /// attribute could not be modified.
+    pub fn into_result(self) -> Result<Vec<Modified>, Error> {
+        match self.failed.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(self.succeeded),
+        }
+    }
+}
+
+impl From<Vec<Response<Modified>>> for Modification {
+    fn from(responses: Vec<Response<Modified>>) -> Self {
+        let mut modification = Self::default();
+        for response in responses {
+            match response {
+                Response::Success(v) => modification.succeeded.push(v),
+                Response::Error(e) => modification.failed.push(e),
+            }
+        }
+        modification
+    }
+}
+
+impl fmt::Display for Modification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self
+            .succeeded
+            .iter()
+            .map(ToString::to_string)
+            .chain(
+                self.failed
+                    .iter()
+                    .map(|e| format!("Failed '{}': {}", e.address, e)),
+            )
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
@@ -0,0 +1,386 @@
+use crate::bridge::RequestType;
+use crate::resource::{group, light, GroupId, LightId};
+use crate::{response, Bridge, Result};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error as ThisError;
+
+/// Attributes that select the color mode of a light, grouped by color mode.
+const COLOR_MODES: [&[&str]; 3] = [&["xy"], &["ct"], &["hue", "sat"]];
+
+/// Kind of a command that is sent to the bridge.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum CommandKind {
+    /// Modifies the state of a light.
+    Light,
+    /// Modifies the state of a group.
+    Group,
+    /// Any other request.
+    Other,
+}
+
+impl CommandKind {
+    /// Returns the kind of a request to an url relative to the API url of a bridge.
+    pub(crate) fn from_request(url_suffix: &str, request_type: &RequestType) -> Self {
+        let parts: Vec<&str> = url_suffix.split('/').collect();
+        match (request_type, parts.as_slice()) {
+            (RequestType::Put(_), ["lights", _, "state"]) => Self::Light,
+            (RequestType::Put(_), ["groups", _, "action"]) => Self::Group,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Metrics of a scheduler.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Metrics {
+    /// Number of commands in the queue that have not been sent yet.
+    pub queue_depth: usize,
+    /// Number of requests that are currently waiting for the rate limit.
+    pub waiting: usize,
+    /// Number of requests that passed the rate limit.
+    pub sent: u64,
+    /// Number of queued commands that were merged into an earlier command.
+    pub coalesced: u64,
+    /// Total time that requests have waited for the rate limit.
+    pub delayed: Duration,
+}
+
+/// Error that occurs when a rate is not a positive number of requests per second.
+#[derive(Clone, Copy, Debug, PartialEq, ThisError)]
+#[error("Rate of {0} requests per second is not a positive number")]
+pub struct RateError(pub f32);
+
+/// Limits the rate of requests to a bridge and queues state changes of lights and groups.
+///
+/// The bridge can only process about 10 light commands and 1 group command per second, further
+/// commands are dropped. A scheduler that is attached to a bridge with [`Bridge::scheduler`]
+/// delays every request until it is allowed by the rate of its [kind]. Requests of the kind
+/// `Other` are not limited unless a rate is set.
+///
+/// State changes can also be queued with [`queue_light_state`] and [`queue_group_state`]. Queued
+/// changes for the same light or group are merged into a single command, where attributes of
+/// later changes replace attributes of earlier ones. A later value also replaces conflicting
+/// attributes, like an increment of the same attribute or an attribute of another color mode,
+/// and increments are added to earlier values. The queue is sent with [`flush`].
+///
+/// A scheduler can be cloned and shared between bridges and threads. All clones use the same
+/// rate limits and queue.
+///
+/// [`Bridge::scheduler`]: ../bridge/struct.Bridge.html#method.scheduler
+/// [kind]: enum.CommandKind.html
+/// [`queue_light_state`]: #method.queue_light_state
+/// [`queue_group_state`]: #method.queue_group_state
+/// [`flush`]: #method.flush
+///
+/// # Examples
+///
+/// ```no_run
+/// use huelib::resource::{light, Modifier};
+/// use huelib::scheduler::Scheduler;
+/// use huelib::Bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let scheduler = Scheduler::new().group_rate(2.0).unwrap();
+/// let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username")
+///     .scheduler(scheduler.clone());
+///
+/// for brightness in (1..=254).step_by(10) {
+///     let modifier = light::StateModifier::new()
+///         .brightness(huelib::resource::ModifierType::Override, brightness);
+///     scheduler.queue_light_state("1", &modifier).unwrap();
+/// }
+/// // The changes are merged into a single command.
+/// scheduler.flush(&bridge).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scheduler {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    intervals: [Option<Duration>; 3],
+    next_slots: [Option<Instant>; 3],
+    queue: Vec<QueuedCommand>,
+    metrics: Metrics,
+}
+
+#[derive(Debug)]
+struct QueuedCommand {
+    kind: CommandKind,
+    id: String,
+    body: Map<String, JsonValue>,
+}
+
+impl Scheduler {
+    /// Creates a new scheduler with 10 light commands and 1 group command per second.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                intervals: [
+                    Some(Duration::from_millis(100)),
+                    Some(Duration::from_secs(1)),
+                    None,
+                ],
+                next_slots: [None; 3],
+                queue: Vec::new(),
+                metrics: Metrics::default(),
+            })),
+        }
+    }
+
+    /// Sets the maximal number of light commands per second.
+    ///
+    /// Returns an error if the rate is not a positive number.
+    pub fn light_rate(self, value: f32) -> std::result::Result<Self, RateError> {
+        self.lock().intervals[0] = Some(interval(value)?);
+        Ok(self)
+    }
+
+    /// Sets the maximal number of group commands per second.
+    ///
+    /// Returns an error if the rate is not a positive number.
+    pub fn group_rate(self, value: f32) -> std::result::Result<Self, RateError> {
+        self.lock().intervals[1] = Some(interval(value)?);
+        Ok(self)
+    }
+
+    /// Sets the maximal number of other requests per second.
+    ///
+    /// Returns an error if the rate is not a positive number.
+    pub fn other_rate(self, value: f32) -> std::result::Result<Self, RateError> {
+        self.lock().intervals[2] = Some(interval(value)?);
+        Ok(self)
+    }
+
+    /// Returns the current metrics of the scheduler.
+    pub fn metrics(&self) -> Metrics {
+        let inner = self.lock();
+        Metrics {
+            queue_depth: inner.queue.len(),
+            ..inner.metrics
+        }
+    }
+
+    /// Queues a state change of a light.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resource::{light, Modifier, ModifierType};
+    /// use huelib::scheduler::Scheduler;
+    ///
+    /// let scheduler = Scheduler::new();
+    /// let on = light::StateModifier::new().on(true);
+    /// let dim = light::StateModifier::new().brightness(ModifierType::Override, 20);
+    /// scheduler.queue_light_state("1", &on).unwrap();
+    /// scheduler.queue_light_state("1", &dim).unwrap();
+    /// scheduler.queue_light_state("2", &on).unwrap();
+    ///
+    /// let metrics = scheduler.metrics();
+    /// assert_eq!(metrics.queue_depth, 2);
+    /// assert_eq!(metrics.coalesced, 1);
+    /// ```
+    ///
+    /// Increments are added to earlier changes of the same attribute.
+    /// ```
+    /// use huelib::resource::{light, Modifier, ModifierType};
+    /// use huelib::scheduler::Scheduler;
+    ///
+    /// let scheduler = Scheduler::new();
+    /// let dim = light::StateModifier::new().brightness(ModifierType::Override, 100);
+    /// let brighten = light::StateModifier::new().brightness(ModifierType::Increment, 20);
+    /// scheduler.queue_light_state("1", &dim).unwrap();
+    /// scheduler.queue_light_state("1", &brighten).unwrap();
+    /// scheduler.queue_light_state("1", &brighten).unwrap();
+    /// assert_eq!(scheduler.metrics().coalesced, 2);
+    /// ```
+    pub fn queue_light_state(
+        &self,
+        id: impl Into<LightId>,
+        modifier: &light::StateModifier,
+    ) -> Result<()> {
+        self.queue(CommandKind::Light, id.into().into_string(), modifier)
+    }
+
+    /// Queues a state change of a group.
+    pub fn queue_group_state(
+        &self,
+        id: impl Into<GroupId>,
+        modifier: &group::StateModifier,
+    ) -> Result<()> {
+        self.queue(CommandKind::Group, id.into().into_string(), modifier)
+    }
+
+    fn queue(&self, kind: CommandKind, id: String, modifier: &impl Serialize) -> Result<()> {
+        let body = match serde_json::to_value(modifier)? {
+            JsonValue::Object(v) => v,
+            _ => Map::new(),
+        };
+        let mut inner = self.lock();
+        match inner
+            .queue
+            .iter_mut()
+            .find(|v| v.kind == kind && v.id == id)
+        {
+            Some(v) => {
+                merge(&mut v.body, body);
+                inner.metrics.coalesced += 1;
+            }
+            None => inner.queue.push(QueuedCommand { kind, id, body }),
+        }
+        Ok(())
+    }
+
+    /// Sends all queued commands to a bridge in the order they were first queued.
+    ///
+    /// The commands are limited by the rates of the scheduler, also if the bridge uses another
+    /// scheduler or none at all. Returns the modifications of all commands, or the first error
+    /// that occurs. Commands that were not sent because of an error are dropped.
+    pub fn flush(&self, bridge: &Bridge) -> Result<Vec<response::Modification>> {
+        let queue = std::mem::take(&mut self.lock().queue);
+        let is_attached = bridge.uses_scheduler(self);
+        let mut modifications = Vec::new();
+        for command in queue {
+            // An attached scheduler already waits when the bridge sends the request.
+            if !is_attached {
+                self.wait(command.kind);
+            }
+            let url = match command.kind {
+                CommandKind::Light => format!("lights/{}/state", command.id),
+                _ => format!("groups/{}/action", command.id),
+            };
+            let body = JsonValue::Object(command.body);
+            modifications.push(bridge.api_request(url, RequestType::Put(body))?);
+        }
+        Ok(modifications)
+    }
+
+    /// Blocks until a request of the given kind is allowed.
+    pub(crate) fn wait(&self, kind: CommandKind) {
+        let delay = {
+            let mut inner = self.lock();
+            let index = match kind {
+                CommandKind::Light => 0,
+                CommandKind::Group => 1,
+                CommandKind::Other => 2,
+            };
+            inner.metrics.sent += 1;
+            let interval = match inner.intervals[index] {
+                Some(v) => v,
+                None => return,
+            };
+            let now = Instant::now();
+            let slot = inner.next_slots[index].map_or(now, |v| v.max(now));
+            inner.next_slots[index] = Some(slot + interval);
+            let delay = slot - now;
+            if !delay.is_zero() {
+                inner.metrics.waiting += 1;
+                inner.metrics.delayed += delay;
+            }
+            delay
+        };
+        if !delay.is_zero() {
+            thread::sleep(delay);
+            self.lock().metrics.waiting -= 1;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Converts a rate in requests per second to the interval between requests.
+fn interval(rate: f32) -> std::result::Result<Duration, RateError> {
+    Duration::try_from_secs_f32(1.0 / rate).map_err(|_| RateError(rate))
+}
+
+/// Merges the attributes of a later state change into a queued command.
+fn merge(body: &mut Map<String, JsonValue>, changes: Map<String, JsonValue>) {
+    let attribute = |key: &str| key.strip_suffix("_inc").unwrap_or(key).to_owned();
+    let color_mode = |key: &str| {
+        let attribute = attribute(key);
+        COLOR_MODES
+            .iter()
+            .position(|v| v.contains(&attribute.as_str()))
+    };
+    for (key, value) in changes {
+        let name = attribute(&key);
+        if let Some(mode) = color_mode(&key) {
+            body.retain(|k, _| color_mode(k).is_none_or(|v| v == mode));
+        }
+        if key.ends_with("_inc") {
+            if let Some(current) = body.get_mut(&name) {
+                *current = apply_increment(&name, current, &value);
+                continue;
+            }
+            if let Some(current) = body.get_mut(&key) {
+                *current = add(current, &value);
+                continue;
+            }
+        } else {
+            body.remove(&format!("{}_inc", key));
+        }
+        body.insert(key, value);
+    }
+}
+
+/// Adds an increment to an absolute value within the range that is accepted by the bridge.
+///
+/// The color temperature is not clamped, because its range depends on the light. The bridge
+/// clamps it to the range of the light.
+fn apply_increment(attribute: &str, value: &JsonValue, increment: &JsonValue) -> JsonValue {
+    match (value, increment) {
+        (JsonValue::Array(v), JsonValue::Array(i)) => add_coordinates(v, i, 0.0, 1.0),
+        (v, i) => {
+            let value = v.as_f64().unwrap_or_default() + i.as_f64().unwrap_or_default();
+            let value = match attribute {
+                "bri" => value.clamp(1.0, 254.0),
+                "sat" => value.clamp(0.0, 254.0),
+                "hue" => value.rem_euclid(65536.0),
+                _ => value,
+            };
+            (value as i64).into()
+        }
+    }
+}
+
+/// Adds two increments.
+fn add(a: &JsonValue, b: &JsonValue) -> JsonValue {
+    match (a, b) {
+        (JsonValue::Array(a), JsonValue::Array(b)) => add_coordinates(a, b, -0.5, 0.5),
+        _ => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => (a + b).into(),
+            _ => (a.as_f64().unwrap_or_default() + b.as_f64().unwrap_or_default()).into(),
+        },
+    }
+}
+
+/// Adds color space coordinates element by element and clamps each of them to a range.
+fn add_coordinates(a: &[JsonValue], b: &[JsonValue], min: f64, max: f64) -> JsonValue {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            (a.as_f64().unwrap_or_default() + b.as_f64().unwrap_or_default()).clamp(min, max)
+        })
+        .collect()
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for Scheduler {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for Scheduler {}
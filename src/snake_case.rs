@@ -0,0 +1,533 @@
+use serde::ser::{self, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+use std::fmt;
+
+/// Field names of the Philips Hue API and the field names of this library, by the serde name of
+/// the struct that contains them.
+///
+/// Structs whose name is used in more than one module are renamed to their path with
+/// `#[serde(rename = "...")]`, fields of struct variants are listed by `<enum>::<variant>`.
+const FIELD_NAMES: &[(&str, &str, &str)] = &[
+    ("Action", "method", "request_type"),
+    ("Backup", "errorcode", "error"),
+    ("ControlCapabilities", "colorgamut", "color_gamut"),
+    ("ControlCapabilities", "colorgamuttype", "color_gamut_type"),
+    ("ControlCapabilities", "ct", "color_temperature"),
+    ("ControlCapabilities", "maxlumen", "max_lumen"),
+    ("ControlCapabilities", "mindimlevel", "min_dimlevel"),
+    ("Group", "modelid", "model_id"),
+    ("Group", "type", "kind"),
+    ("InternetServices", "remoteaccess", "remote_access"),
+    ("InternetServices", "swupdate", "software_update"),
+    ("Light", "manufacturername", "manufacturer_name"),
+    ("Light", "modelid", "model_id"),
+    ("Light", "productid", "product_id"),
+    ("Light", "productname", "product_name"),
+    ("Light", "swupdate", "software_update"),
+    ("Light", "swversion", "software_version"),
+    ("Light", "type", "kind"),
+    ("Light", "uniqueid", "unique_id"),
+    ("LightStateModifier", "bri", "brightness"),
+    ("LightStateModifier", "ct", "color_temperature"),
+    ("LightStateModifier", "sat", "saturation"),
+    ("LightStateModifier", "transitiontime", "transition_time"),
+    ("LightStateModifier", "xy", "color_space_coordinates"),
+    ("Resourcelink", "classid", "class_id"),
+    ("Resourcelink", "type", "kind"),
+    ("Rule", "lasttriggered", "last_triggered"),
+    ("Rule", "timestriggered", "times_triggered"),
+    ("Scene", "appdata", "app_data"),
+    ("Scene", "lastupdate", "last_update"),
+    ("Scene", "lightstates", "light_states"),
+    ("Scene", "type", "kind"),
+    ("ScenesInfo", "lightstates", "light_states"),
+    ("Schedule", "autodelete", "auto_delete"),
+    ("Schedule", "command", "action"),
+    ("Schedule", "localtime", "local_time"),
+    ("Schedule", "starttime", "start_time"),
+    ("Sensor", "manufacturername", "manufacturer_name"),
+    ("Sensor", "modelid", "model_id"),
+    ("Sensor", "swversion", "software_verion"),
+    ("Sensor", "type", "type_name"),
+    ("Sensor", "uniqueid", "unique_id"),
+    ("SoftwareUpdateAutoInstall", "updatetime", "update_time"),
+    ("User", "create date", "create_date"),
+    ("User", "last use date", "last_use_date"),
+    ("config::Config", "UTC", "current_time"),
+    ("config::Config", "apiversion", "api_version"),
+    ("config::Config", "bridgeid", "bridge_id"),
+    ("config::Config", "datastoreversion", "datastore_version"),
+    ("config::Config", "factorynew", "factory_new"),
+    ("config::Config", "internetservices", "internet_services"),
+    ("config::Config", "ipaddress", "ip_address"),
+    ("config::Config", "linkbutton", "link_button"),
+    ("config::Config", "localtime", "local_time"),
+    ("config::Config", "mac", "mac_address"),
+    ("config::Config", "modelid", "model_id"),
+    ("config::Config", "portalconnection", "portal_connection"),
+    ("config::Config", "portalservices", "portal_services"),
+    ("config::Config", "portalstate", "portal_state"),
+    ("config::Config", "replacesbridgeid", "replaces_bridge_id"),
+    ("config::Config", "starterkitid", "starterkit_id"),
+    ("config::Config", "swupdate2", "software_update"),
+    ("config::Config", "swversion", "software_version"),
+    ("config::Config", "zigbeechannel", "zigbee_channel"),
+    ("config::SoftwareUpdate", "autoinstall", "auto_install"),
+    ("config::SoftwareUpdate", "checkforupdate", "check"),
+    ("config::SoftwareUpdate", "lastchange", "last_change"),
+    ("config::SoftwareUpdate", "lastinstall", "last_install"),
+    ("light::Config", "archetype", "arche_type"),
+    ("light::SoftwareUpdate", "lastinstall", "last_install"),
+    ("light::State", "bri", "brightness"),
+    ("light::State", "colormode", "color_mode"),
+    ("light::State", "ct", "color_temperature"),
+    ("light::State", "sat", "saturation"),
+    ("light::State", "xy", "color_space_coordinates"),
+    ("sensor::State", "lastupdated", "last_updated"),
+];
+
+/// Returns the field name of this library for a field name of the Philips Hue API.
+///
+/// The variant is only set for fields of struct variants.
+fn field_name(container: &str, variant: Option<&str>, key: &'static str) -> &'static str {
+    FIELD_NAMES
+        .iter()
+        .find(|(c, k, _)| {
+            let is_container = match variant {
+                Some(v) => c.split_once("::") == Some((container, v)),
+                None => *c == container,
+            };
+            is_container && *k == key
+        })
+        .map_or(key, |(_, _, v)| v)
+}
+
+/// Wrapper that serializes a resource with the field names of this library.
+///
+/// Resources are serialized with the field names of the Philips Hue API by default, so that they
+/// can be sent to a bridge or read by other tools. This wrapper replaces them with the snake case
+/// field names of the structs of this library, for example `model_id` instead of `modelid`. It
+/// works with every serializer and renames the fields of nested resources as well. The field
+/// names of the API are still expected when deserializing.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::resource::Resourcelink;
+/// use huelib::snake_case::SnakeCase;
+///
+/// let resourcelink: Resourcelink = serde_json::from_str(r#"{
+///     "name": "Routine",
+///     "description": "",
+///     "owner": "username",
+///     "type": "Link",
+///     "classid": 1,
+///     "recycle": false,
+///     "links": ["/groups/1"]
+/// }"#).unwrap();
+///
+/// let value = serde_json::to_value(SnakeCase(&resourcelink)).unwrap();
+/// assert_eq!(value["class_id"], 1);
+/// assert_eq!(value["kind"], "Link");
+/// assert!(value.get("classid").is_none());
+/// ```
+///
+/// Every field of the resources is renamed to the name of the field in this library, which is
+/// the name that is written by the `Debug` implementation.
+/// ```
+/// use huelib::resource::{
+///     Capabilities, Config, Group, Light, Resourcelink, Rule, Scene, Schedule, Sensor,
+/// };
+/// use huelib::snake_case;
+/// use serde_json::Value;
+/// use std::collections::HashSet;
+///
+/// fn check<T: serde::Serialize + std::fmt::Debug>(resource: &T) {
+///     // Field names and string values as written by the `Debug` implementation.
+///     let debug = format!("{:?}", resource);
+///     let mut names: HashSet<String> = debug
+///         .split(": ")
+///         .filter_map(|v| v.rsplit(|c: char| !c.is_alphanumeric() && c != '_').next())
+///         .map(String::from)
+///         .collect();
+///     names.extend(debug.split('"').skip(1).step_by(2).map(String::from));
+///     fn keys(value: &Value, out: &mut Vec<String>) {
+///         match value {
+///             Value::Object(v) => v.iter().for_each(|(k, v)| {
+///                 out.push(k.clone());
+///                 keys(v, out);
+///             }),
+///             Value::Array(v) => v.iter().for_each(|v| keys(v, out)),
+///             _ => {}
+///         }
+///     }
+///     let mut found = Vec::new();
+///     keys(&snake_case::to_value(resource).unwrap(), &mut found);
+///     for key in found {
+///         assert!(names.contains(&key), "{} of {:?}", key, resource);
+///     }
+/// }
+///
+/// # let light: Light = serde_json::from_str(r#"{"name": "Desk", "type": "Extended color light",
+/// #     "state": {"on": true, "bri": 254, "hue": 0, "sat": 0, "xy": [0.3, 0.3], "ct": 153,
+/// #     "alert": "none", "effect": "none", "colormode": "xy", "reachable": true},
+/// #     "modelid": "LCT015", "uniqueid": "00:17", "productid": null, "productname": null,
+/// #     "manufacturername": "Signify", "swversion": "1.0",
+/// #     "swupdate": {"state": "noupdates", "lastinstall": null},
+/// #     "config": {"archetype": "sultanbulb", "function": "mixed",
+/// #     "direction": "omnidirectional"},
+/// #     "capabilities": {"certified": true, "streaming": {"renderer": true, "proxy": true},
+/// #     "control": {"mindimlevel": 1000, "maxlumen": 800, "colorgamuttype": "C",
+/// #     "colorgamut": [[0.69, 0.3], [0.17, 0.7], [0.15, 0.06]],
+/// #     "ct": {"min": 153, "max": 500}}}}"#).unwrap();
+/// check(&light);
+/// # let group: Group = serde_json::from_str(r#"{"name": "Kitchen", "lights": ["1"], "sensors": [],
+/// #     "type": "Room", "class": "Kitchen", "state": {"all_on": false, "any_on": true},
+/// #     "modelid": null, "recycle": false}"#).unwrap();
+/// check(&group);
+/// # let scene: Scene = serde_json::from_str(r#"{"name": "Bright", "type": "GroupScene",
+/// #     "group": "1", "lights": ["1"], "owner": "user", "recycle": false, "locked": false,
+/// #     "appdata": {"version": 1, "data": "app"}, "picture": "", "lastupdate": null,
+/// #     "version": 2, "lightstates": {"1": {"on": true, "bri": 254, "sat": 1, "xy": [0.3, 0.3],
+/// #     "ct": 153, "transitiontime": 4}}}"#).unwrap();
+/// check(&scene);
+/// # let schedule: Schedule = serde_json::from_str(r#"{"name": "Wake up", "description": "",
+/// #     "command": {"address": "/api/user/groups/1/action", "method": "PUT",
+/// #     "body": {"on": true}},
+/// #     "localtime": "W124/T07:00:00", "starttime": null, "status": "enabled",
+/// #     "autodelete": false}"#).unwrap();
+/// check(&schedule);
+/// # let rule: Rule = serde_json::from_str(r#"{"name": "Switch", "owner": "user",
+/// #     "lasttriggered": "none", "timestriggered": 0, "created": "2020-01-01T00:00:00",
+/// #     "status": "enabled", "conditions": [{"address": "/sensors/2/state/buttonevent",
+/// #     "operator": "eq", "value": "1002"}], "actions": [{"address": "/groups/1/action",
+/// #     "method": "PUT", "body": {"on": true}}]}"#).unwrap();
+/// check(&rule);
+/// # let sensor: Sensor = serde_json::from_str(r#"{"name": "Daylight", "type": "Daylight",
+/// #     "modelid": "PHDL00", "uniqueid": null, "manufacturername": "Signify", "swversion": "1.0",
+/// #     "state": {"presence": null, "flag": null, "lastupdated": "none"},
+/// #     "config": {"on": true, "reachable": null, "battery": null}, "recycle": null}"#).unwrap();
+/// check(&sensor);
+/// # let resourcelink: Resourcelink = serde_json::from_str(r#"{"name": "Routine",
+/// #     "description": "", "owner": "user", "type": "Link", "classid": 1, "recycle": false,
+/// #     "links": ["/groups/1"]}"#).unwrap();
+/// check(&resourcelink);
+/// # let config: Config = serde_json::from_str(r#"{"name": "Bridge", "swupdate2": {
+/// #     "state": "noupdates", "checkforupdate": false, "lastchange": null, "lastinstall": null,
+/// #     "autoinstall": {"on": true, "updatetime": "T14:00:00"}}, "swversion": "1",
+/// #     "apiversion": "1.0", "linkbutton": false, "ipaddress": "192.168.1.2", "mac": "00:17",
+/// #     "netmask": "255.255.255.0", "gateway": "192.168.1.1", "dhcp": true,
+/// #     "portalservices": true, "portalconnection": "connected",
+/// #     "portalstate": {"signedon": true, "incoming": false, "outgoing": true,
+/// #     "communication": "disconnected"}, "internetservices": {"internet": "connected",
+/// #     "remoteaccess": "connected", "time": "connected", "swupdate": "connected"},
+/// #     "UTC": "2020-01-01T00:00:00", "localtime": "none", "timezone": "none",
+/// #     "zigbeechannel": 15, "modelid": "BSB002", "bridgeid": "001788FFFE000000",
+/// #     "factorynew": false, "replacesbridgeid": null, "datastoreversion": "1",
+/// #     "starterkitid": "", "backup": {"status": "idle", "errorcode": 0}, "whitelist": {
+/// #     "abc": {"name": "app", "last use date": "2020-01-01T00:00:00",
+/// #     "create date": "2020-01-01T00:00:00"}}}"#).unwrap();
+/// check(&config);
+/// # let capabilities: Capabilities = serde_json::from_str(r#"{
+/// #     "lights": {"available": 1, "total": 63},
+/// #     "groups": {"available": 1, "total": 64}, "sensors": {"available": 1, "total": 250,
+/// #     "clip": {"available": 1, "total": 250}, "zll": {"available": 1, "total": 64},
+/// #     "zgp": {"available": 1, "total": 64}}, "scenes": {"available": 1, "total": 200,
+/// #     "lightstates": {"available": 1, "total": 2048}},
+/// #     "schedules": {"available": 1, "total": 100},
+/// #     "rules": {"available": 1, "total": 250, "conditions": {"available": 1, "total": 1500},
+/// #     "actions": {"available": 1, "total": 1000}},
+/// #     "resourcelinks": {"available": 1, "total": 64},
+/// #     "streaming": {"available": 1, "total": 1, "channels": 10},
+/// #     "timezones": {"values": []}}"#).unwrap();
+/// check(&capabilities);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SnakeCase<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Serialize + ?Sized> Serialize for SnakeCase<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(Renamer(serializer))
+    }
+}
+
+/// Converts a resource into a JSON value with the field names of this library.
+///
+/// See [`SnakeCase`] for more information.
+///
+/// [`SnakeCase`]: struct.SnakeCase.html
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<JsonValue> {
+    serde_json::to_value(SnakeCase(value))
+}
+
+/// Serializer that renames the fields of structs before passing them to another serializer.
+struct Renamer<S>(S);
+
+/// Compound serializer that renames fields and the fields of nested values.
+struct Compound<C> {
+    inner: C,
+    container: &'static str,
+    variant: Option<&'static str>,
+}
+
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method(self, $($arg: $ty),*) -> Result<Self::Ok, Self::Error> {
+                self.0.$method($($arg),*)
+            }
+        )*
+    };
+}
+
+impl<S: Serializer> Serializer for Renamer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Compound<S::SerializeSeq>;
+    type SerializeTuple = Compound<S::SerializeTuple>;
+    type SerializeTupleStruct = Compound<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Compound<S::SerializeTupleVariant>;
+    type SerializeMap = Compound<S::SerializeMap>;
+    type SerializeStruct = Compound<S::SerializeStruct>;
+    type SerializeStructVariant = Compound<S::SerializeStructVariant>;
+
+    forward! {
+        serialize_bool(v: bool);
+        serialize_i8(v: i8);
+        serialize_i16(v: i16);
+        serialize_i32(v: i32);
+        serialize_i64(v: i64);
+        serialize_u8(v: u8);
+        serialize_u16(v: u16);
+        serialize_u32(v: u32);
+        serialize_u64(v: u64);
+        serialize_f32(v: f32);
+        serialize_f64(v: f64);
+        serialize_char(v: char);
+        serialize_str(v: &str);
+        serialize_bytes(v: &[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(name: &'static str);
+        serialize_unit_variant(name: &'static str, index: u32, variant: &'static str);
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_some(&SnakeCase(value))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_struct(name, &SnakeCase(value))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0
+            .serialize_newtype_variant(name, index, variant, &SnakeCase(value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(Compound::new(self.0.serialize_seq(len)?, ""))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Ok(Compound::new(self.0.serialize_tuple(len)?, ""))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Ok(Compound::new(
+            self.0.serialize_tuple_struct(name, len)?,
+            name,
+        ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        let inner = self.0.serialize_tuple_variant(name, index, variant, len)?;
+        Ok(Compound::new(inner, name))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Ok(Compound::new(self.0.serialize_map(len)?, ""))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Ok(Compound::new(self.0.serialize_struct(name, len)?, name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        let inner = self.0.serialize_struct_variant(name, index, variant, len)?;
+        Ok(Compound::variant(inner, name, variant))
+    }
+
+    fn collect_str<T: fmt::Display + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.collect_str(value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+impl<C> Compound<C> {
+    fn new(inner: C, container: &'static str) -> Self {
+        Self {
+            inner,
+            container,
+            variant: None,
+        }
+    }
+
+    fn variant(inner: C, container: &'static str, variant: &'static str) -> Self {
+        Self {
+            inner,
+            container,
+            variant: Some(variant),
+        }
+    }
+
+    fn field_name(&self, key: &'static str) -> &'static str {
+        field_name(self.container, self.variant, key)
+    }
+}
+
+impl<C: ser::SerializeSeq> ser::SerializeSeq for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner.serialize_element(&SnakeCase(value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTuple> ser::SerializeTuple for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner.serialize_element(&SnakeCase(value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTupleStruct> ser::SerializeTupleStruct for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner.serialize_field(&SnakeCase(value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTupleVariant> ser::SerializeTupleVariant for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner.serialize_field(&SnakeCase(value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeMap> ser::SerializeMap for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), C::Error> {
+        self.inner.serialize_key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner.serialize_value(&SnakeCase(value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeStruct> ser::SerializeStruct for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        let key = self.field_name(key);
+        self.inner.serialize_field(key, &SnakeCase(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        self.inner.skip_field(self.field_name(key))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeStructVariant> ser::SerializeStructVariant for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        let key = self.field_name(key);
+        self.inner.serialize_field(key, &SnakeCase(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        self.inner.skip_field(self.field_name(key))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use thiserror::Error as ThisError;
+
+/// Host name that is used for HTTPS connections to a bridge.
+///
+/// The host name is resolved to the IP address of the bridge and is not part of the certificate
+/// validation.
+pub(crate) const HOST_NAME: &str = "hue-bridge";
+
+/// SHA-256 fingerprint of a certificate.
+///
+/// # Examples
+///
+/// Fingerprints are parsed from hexadecimal bytes that may be separated by colons.
+/// ```
+/// use huelib::tls::Fingerprint;
+///
+/// let hex = "AB:".repeat(31) + "CD";
+/// let fingerprint: Fingerprint = hex.parse().unwrap();
+/// assert_eq!(fingerprint.to_string(), hex);
+/// assert!(("+F".repeat(31) + "CD").parse::<Fingerprint>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Fingerprint(pub [u8; 32]);
+
+impl Fingerprint {
+    /// Computes the fingerprint of a DER encoded certificate.
+    pub fn of(certificate: &[u8]) -> Self {
+        let digest = ring::digest::digest(&ring::digest::SHA256, certificate);
+        let mut value = [0; 32];
+        value.copy_from_slice(digest.as_ref());
+        Self(value)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hex: Vec<String> = self.0.iter().map(|v| format!("{:02X}", v)).collect();
+        write!(f, "{}", hex.join(":"))
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = CertificateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex: String = s.chars().filter(|c| *c != ':').collect();
+        if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CertificateError::InvalidFingerprint(s.to_owned()));
+        }
+        let mut value = [0; 32];
+        for (i, v) in value.iter_mut().enumerate() {
+            *v = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .map_err(|_| CertificateError::InvalidFingerprint(s.to_owned()))?;
+        }
+        Ok(Self(value))
+    }
+}
+
+/// Errors that can occur while validating the certificate of a bridge.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum CertificateError {
+    /// The certificate could not be parsed.
+    #[error("Failed to parse certificate")]
+    Parse,
+    /// The bridge did not present a certificate.
+    #[error("Bridge did not present a certificate")]
+    Missing,
+    /// The certificate is not valid at the current time.
+    #[error("Certificate is expired or not yet valid")]
+    Expired,
+    /// The common name of the certificate does not match the bridge id.
+    #[error("Certificate belongs to bridge '{found}', expected '{expected}'")]
+    BridgeId {
+        /// Expected bridge id.
+        expected: String,
+        /// Common name of the certificate.
+        found: String,
+    },
+    /// The fingerprint of the certificate does not match the pinned fingerprint.
+    #[error("Certificate fingerprint {found} does not match the pinned fingerprint {expected}")]
+    Fingerprint {
+        /// Pinned fingerprint.
+        expected: Fingerprint,
+        /// Fingerprint of the certificate.
+        found: Fingerprint,
+    },
+    /// Neither a bridge id, a fingerprint nor trust on first use is configured.
+    #[error("No certificate pin is configured")]
+    NoPin,
+    /// A fingerprint string is invalid.
+    #[error("Invalid fingerprint: {0}")]
+    InvalidFingerprint(String),
+}
+
+/// Validates the certificate of a bridge.
+///
+/// Bridges serve HTTPS with a certificate whose common name is the bridge id. The verifier can
+/// pin the bridge id, which is available from [`Config::bridge_id`] or [discovery], the
+/// fingerprint of the certificate, or both. With trust on first use, the fingerprint of the
+/// first certificate is pinned and can be retrieved with [`pinned_fingerprint`] to store it for
+/// later connections.
+///
+/// Anyone can create a certificate with the common name of a bridge, so pinning the bridge id
+/// alone only protects against connecting to the wrong bridge. Therefore a verifier with a bridge
+/// id also pins the fingerprint of the first accepted certificate, like with trust on first use,
+/// and rejects any other certificate afterwards. Pinning a known fingerprint protects the first
+/// connection as well.
+///
+/// [`Config::bridge_id`]: ../resource/config/struct.Config.html#structfield.bridge_id
+/// [discovery]: ../bridge/fn.discover_bridges.html
+/// [`pinned_fingerprint`]: #method.pinned_fingerprint
+///
+/// # Examples
+///
+/// Validate a self-signed certificate.
+/// ```
+/// use huelib::tls::{CertificateError, CertificateVerifier};
+///
+/// let mut params = rcgen::CertificateParams::new(vec![]);
+/// params
+///     .distinguished_name
+///     .push(rcgen::DnType::CommonName, "001788fffe23bfc2");
+/// let certificate = rcgen::Certificate::from_params(params).unwrap();
+/// let der = certificate.serialize_der().unwrap();
+///
+/// let verifier = CertificateVerifier::new().bridge_id("001788FFFE23BFC2");
+/// assert_eq!(verifier.verify(&der), Ok(()));
+/// assert!(verifier.pinned_fingerprint().is_some());
+///
+/// let verifier = CertificateVerifier::new().bridge_id("001788FFFE000000");
+/// assert!(matches!(verifier.verify(&der), Err(CertificateError::BridgeId { .. })));
+///
+/// let verifier = CertificateVerifier::new().trust_on_first_use(true);
+/// assert_eq!(verifier.verify(&der), Ok(()));
+/// assert!(verifier.pinned_fingerprint().is_some());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CertificateVerifier {
+    bridge_id: Option<String>,
+    trust_on_first_use: bool,
+    fingerprint: Arc<Mutex<Option<Fingerprint>>>,
+}
+
+impl CertificateVerifier {
+    /// Creates a new certificate verifier without any pins.
+    ///
+    /// A verifier without pins rejects all certificates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pins the bridge id that must be the common name of the certificate.
+    ///
+    /// The bridge id is compared case-insensitively. The fingerprint of the first certificate
+    /// with this bridge id is pinned if no fingerprint is pinned yet.
+    pub fn bridge_id(mut self, value: impl Into<String>) -> Self {
+        self.bridge_id = Some(value.into());
+        self
+    }
+
+    /// Pins the fingerprint of the certificate.
+    pub fn fingerprint(self, value: Fingerprint) -> Self {
+        self.set_fingerprint(value);
+        self
+    }
+
+    /// Sets whether the fingerprint of the first certificate is pinned if no fingerprint is
+    /// pinned yet.
+    pub fn trust_on_first_use(mut self, value: bool) -> Self {
+        self.trust_on_first_use = value;
+        self
+    }
+
+    /// Returns the pinned fingerprint.
+    ///
+    /// With trust on first use, this is the fingerprint of the first certificate that was
+    /// accepted.
+    pub fn pinned_fingerprint(&self) -> Option<Fingerprint> {
+        *self.lock()
+    }
+
+    /// Validates a DER encoded certificate.
+    pub fn verify(&self, certificate: &[u8]) -> Result<(), CertificateError> {
+        let pinned = *self.lock();
+        if self.bridge_id.is_none() && pinned.is_none() && !self.trust_on_first_use {
+            return Err(CertificateError::NoPin);
+        }
+        let (_, parsed) = x509_parser::parse_x509_certificate(certificate)
+            .map_err(|_| CertificateError::Parse)?;
+        if !parsed.validity().is_valid() {
+            return Err(CertificateError::Expired);
+        }
+        if let Some(expected) = &self.bridge_id {
+            let found = parsed
+                .subject()
+                .iter_common_name()
+                .next()
+                .and_then(|v| v.as_str().ok())
+                .unwrap_or_default();
+            if !found.eq_ignore_ascii_case(expected) {
+                return Err(CertificateError::BridgeId {
+                    expected: expected.clone(),
+                    found: found.to_owned(),
+                });
+            }
+        }
+        let found = Fingerprint::of(certificate);
+        match pinned {
+            Some(expected) if expected != found => {
+                Err(CertificateError::Fingerprint { expected, found })
+            }
+            Some(_) => Ok(()),
+            None => {
+                if self.trust_on_first_use || self.bridge_id.is_some() {
+                    self.set_fingerprint(found);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn set_fingerprint(&self, value: Fingerprint) {
+        *self.lock() = Some(value);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Fingerprint>> {
+        self.fingerprint.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl PartialEq for CertificateVerifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.bridge_id == other.bridge_id
+            && self.trust_on_first_use == other.trust_on_first_use
+            && Arc::ptr_eq(&self.fingerprint, &other.fingerprint)
+    }
+}
+
+impl Eq for CertificateVerifier {}
+
+impl rustls::ServerCertVerifier for CertificateVerifier {
+    fn verify_server_cert(
+        &self,
+        _roots: &rustls::RootCertStore,
+        presented_certs: &[rustls::Certificate],
+        _dns_name: webpki::DNSNameRef,
+        _ocsp_response: &[u8],
+    ) -> Result<rustls::ServerCertVerified, rustls::TLSError> {
+        let certificate = presented_certs
+            .first()
+            .ok_or_else(|| rustls::TLSError::General(CertificateError::Missing.to_string()))?;
+        self.verify(&certificate.0)
+            .map_err(|e| rustls::TLSError::General(e.to_string()))?;
+        Ok(rustls::ServerCertVerified::assertion())
+    }
+}
+
+/// Connection settings for HTTPS requests to a bridge.
+#[derive(Clone)]
+pub(crate) struct Connection {
+    pub(crate) agent: ureq::Agent,
+    pub(crate) config: Arc<rustls::ClientConfig>,
+    verifier: CertificateVerifier,
+}
+
+impl Connection {
+    pub(crate) fn new(ip_address: IpAddr, port: u16, verifier: CertificateVerifier) -> Self {
+        let mut config = rustls::ClientConfig::new();
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(verifier.clone()));
+        let mut agent = ureq::agent();
+        agent.set_resolver(move |_: &str| -> io::Result<Vec<SocketAddr>> {
+            Ok(vec![SocketAddr::new(ip_address, port)])
+        });
+        Self {
+            agent,
+            config: Arc::new(config),
+            verifier,
+        }
+    }
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("verifier", &self.verifier)
+            .finish()
+    }
+}
+
+impl PartialEq for Connection {
+    fn eq(&self, other: &Self) -> bool {
+        self.verifier == other.verifier
+    }
+}
+
+impl Eq for Connection {}
@@ -1,4 +1,5 @@
 use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
 
 pub(crate) fn deserialize_option_string<'de, D: Deserializer<'de>>(
     deserializer: D,
@@ -31,3 +32,30 @@ pub(crate) fn deserialize_option_time<'de, D: Deserializer<'de>>(
         _ => None,
     })
 }
+
+pub(crate) fn serialize_option_string<S: Serializer>(
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(value.as_deref().unwrap_or("none"))
+}
+
+pub(crate) fn serialize_option_date_time<S: Serializer>(
+    value: &Option<chrono::NaiveDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => v.serialize(serializer),
+        None => serializer.serialize_str("none"),
+    }
+}
+
+pub(crate) fn serialize_option_time<S: Serializer>(
+    value: &Option<chrono::NaiveTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serializer.serialize_str(&format!("T{}", v)),
+        None => serializer.serialize_str("none"),
+    }
+}